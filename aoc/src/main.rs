@@ -0,0 +1,974 @@
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::PathBuf,
+    process, thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_utils::{
+    bench_solution, dump_frames, exit_with_error, find_workspace_root, get_input_file_name, init_tracing, normalize_input, play, print_bench, print_day_header, run_day_binary, run_parallel,
+    run_solution, set_color_enabled, split_trace_args, try_read_input, verify_solution, watched_paths, write_changes, write_summary, AocClient, BenchReport, DaySelection, DayStatus, DynSolution,
+    ExpectedAnswers, ImageExport, InputError, InputGenerator, InputSource, Outcome, OutputFormat, Part, ResultPrinter, RunError, SubmissionLog, Verdict, Visualization, Watcher, WATCH_INTERVAL,
+};
+
+mod scaffold;
+
+/// All solutions, ordered by day
+const SOLUTIONS: &[&dyn DynSolution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+/// Generators of random inputs, ordered by day
+const GENERATORS: &[InputGenerator] = &[
+    day01::generator::GENERATOR,
+    day02::generator::GENERATOR,
+    day04::generator::GENERATOR,
+    day05::generator::GENERATOR,
+    day16::generator::GENERATOR,
+    day18::generator::GENERATOR,
+    day19::generator::GENERATOR,
+    day22::generator::GENERATOR,
+];
+
+/// Animations of simulations, ordered by day
+const VISUALIZATIONS: &[Visualization] = &[
+    day05::animation::VISUALIZATION,
+    day20::animation::VISUALIZATION,
+    day23::animation::VISUALIZATION,
+    day25::animation::VISUALIZATION,
+];
+
+/// Image exports of puzzle states, ordered by day
+const EXPORTS: &[ImageExport] = &[day05::export::EXPORT, day17::export::EXPORT, day20::export::EXPORT, day25::export::EXPORT];
+
+/// Frames per second of `aoc visualize` if `--fps` is not given
+const DEFAULT_FPS: f64 = 10.0;
+
+const USAGE: &str = "Usage: aoc <day|all|from..=to> [--timeout <seconds>] [--part <1|2>] [--input <path|->] [--format <human|plain|json|csv|markdown>]
+       aoc <day|all|from..=to> --parallel [--jobs <n>] [--timeout <seconds>] [--part <1|2>] [--input <path|->] [--format <human|plain>]
+       aoc <day|all|from..=to> --verify [--part <1|2>] [--input <path|->] [--format <human|plain>]
+       aoc <day|all|from..=to> --bench [--format <human|plain>] [--runs <n>] [--report <path|->] [--input <path|->]
+       aoc new <day>
+       aoc fetch <day|all|from..=to>
+       aoc submit <day> <1|2>
+       aoc watch <day>
+       aoc gen <day> [--seed <n>] [--size <n>]
+       aoc visualize <day> [--input <path|->] [--fps <n>] [--dump <dir>] [--frames <n>]
+       aoc export <day> [--input <path|->] [--output <path>]
+All commands accept --trace (trace to stderr) or --trace=<path> (JSON lines file), also set with AOC_TRACE=<stderr|path>";
+
+/// Number of runs per day if `--runs` is not given
+const DEFAULT_RUNS: usize = 10;
+
+/// Benchmark options of the runner
+#[derive(Debug, PartialEq, Eq)]
+struct BenchOptions {
+    runs: usize,
+    /// File for the JSON report, `-` for stdout
+    report: Option<PathBuf>,
+}
+
+/// Options for solving days concurrently
+#[derive(Debug, PartialEq, Eq)]
+struct ParallelOptions {
+    /// Number of worker threads
+    jobs: usize,
+}
+
+/// Subcommand selected on the command line
+enum Command {
+    /// Solve, verify or benchmark days
+    Run(Options),
+    /// Generate the crate for a new day
+    New(i32),
+    /// Download the inputs of the given days
+    Fetch(DaySelection),
+    /// Solve a part and submit its answer
+    Submit(i32, Part),
+    /// Rebuild and solve a day whenever its sources or input change
+    Watch(i32),
+    /// Print a random input for a day
+    Gen(GenOptions),
+    /// Animate the simulation of a day in the terminal or dump its frames
+    Visualize(VisualizeOptions),
+    /// Write an image of the puzzle state of a day
+    Export(ExportOptions),
+}
+
+/// Options for generating an input
+#[derive(Debug, PartialEq, Eq)]
+struct GenOptions {
+    day: i32,
+    /// Random seed, based on the current time if not given
+    seed: Option<u64>,
+    /// Size of the input, the size of the real input if not given
+    size: Option<usize>,
+}
+
+/// Options for animating a day
+#[derive(Debug, PartialEq)]
+struct VisualizeOptions {
+    day: i32,
+    input: Option<InputSource>,
+    /// Initial speed of the player
+    fps: f64,
+    /// Directory to write the frames to instead of playing them
+    dump: Option<PathBuf>,
+    /// Maximum number of dumped frames
+    frames: Option<usize>,
+}
+
+/// Options for exporting an image
+#[derive(Debug, PartialEq, Eq)]
+struct ExportOptions {
+    day: i32,
+    input: Option<InputSource>,
+    /// Image file, next to the input file if not given
+    output: Option<PathBuf>,
+}
+
+/// Command line options of the runner
+struct Options {
+    days: DaySelection,
+    part: Option<Part>,
+    input: Option<InputSource>,
+    bench: Option<BenchOptions>,
+    parallel: Option<ParallelOptions>,
+    /// Days running longer are reported as timed out
+    timeout: Option<Duration>,
+    format: OutputFormat,
+    /// Compare the answers with `answers.toml`
+    verify: bool,
+}
+
+/// Parses the command line (without the program name) into a subcommand
+fn parse_command(args: Vec<String>) -> Result<Command, String> {
+    match args.first().map(String::as_str) {
+        Some("new") => match &args[1..] {
+            [day] => parse_day(day).map(Command::New),
+            [] => Err("missing day".to_string()),
+            _ => Err(format!("unexpected argument '{}'", args[2])),
+        },
+        Some("fetch") => match &args[1..] {
+            [days] => days.parse().map(Command::Fetch),
+            [] => Err("missing day selection".to_string()),
+            _ => Err(format!("unexpected argument '{}'", args[2])),
+        },
+        Some("submit") => match &args[1..] {
+            [day, part] => Ok(Command::Submit(parse_day(day)?, part.parse()?)),
+            [_] => Err("missing part".to_string()),
+            [] => Err("missing day".to_string()),
+            _ => Err(format!("unexpected argument '{}'", args[3])),
+        },
+        Some("watch") => match &args[1..] {
+            [day] => parse_day(day).map(Command::Watch),
+            [] => Err("missing day".to_string()),
+            _ => Err(format!("unexpected argument '{}'", args[2])),
+        },
+        Some("gen") => parse_gen(args.into_iter().skip(1)).map(Command::Gen),
+        Some("visualize") => parse_visualize(args.into_iter().skip(1)).map(Command::Visualize),
+        Some("export") => parse_export(args.into_iter().skip(1)).map(Command::Export),
+        _ => parse_args(args).map(Command::Run),
+    }
+}
+
+/// Parses a single day
+fn parse_day(day: &str) -> Result<i32, String> {
+    day.parse()
+        .ok()
+        .filter(|d| (1..=25).contains(d))
+        .ok_or_else(|| format!("invalid day '{}', expected a number between 1 and 25", day))
+}
+
+/// Parses the options for generating an input
+fn parse_gen(args: impl IntoIterator<Item = String>) -> Result<GenOptions, String> {
+    let mut day = None;
+    let mut seed = None;
+    let mut size = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+
+        match name.as_str() {
+            "--seed" | "--size" => {
+                let value = value.or_else(|| args.next()).ok_or_else(|| format!("missing value for {}", name))?;
+                match name.as_str() {
+                    "--seed" => seed = Some(value.parse::<u64>().map_err(|_| format!("invalid seed '{}'", value))?),
+                    _ => size = Some(value.parse::<usize>().ok().filter(|s| *s > 0).ok_or_else(|| format!("invalid size '{}'", value))?),
+                }
+            }
+            _ if name.starts_with("--") => return Err(format!("unknown option '{}'", name)),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(GenOptions {
+        day: day.ok_or("missing day")?,
+        seed,
+        size,
+    })
+}
+
+/// Parses the options for animating a day
+fn parse_visualize(args: impl IntoIterator<Item = String>) -> Result<VisualizeOptions, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut fps = None;
+    let mut dump = None;
+    let mut frames = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+
+        match name.as_str() {
+            "--input" | "--fps" | "--dump" | "--frames" => {
+                let value = value.or_else(|| args.next()).ok_or_else(|| format!("missing value for {}", name))?;
+                match name.as_str() {
+                    "--input" => input = Some(if value == "-" { InputSource::Stdin } else { InputSource::File(PathBuf::from(value)) }),
+                    "--fps" => fps = Some(value.parse::<f64>().ok().filter(|f| f.is_finite() && *f > 0.0).ok_or_else(|| format!("invalid fps '{}'", value))?),
+                    "--dump" => dump = Some(PathBuf::from(value)),
+                    _ => frames = Some(value.parse::<usize>().ok().filter(|f| *f > 0).ok_or_else(|| format!("invalid number of frames '{}'", value))?),
+                }
+            }
+            _ if name.starts_with("--") => return Err(format!("unknown option '{}'", name)),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    if dump.is_some() && fps.is_some() {
+        return Err("--fps cannot be combined with --dump".to_string());
+    }
+
+    if dump.is_none() && frames.is_some() {
+        return Err("--frames requires --dump".to_string());
+    }
+
+    if dump.is_none() && input == Some(InputSource::Stdin) {
+        // The keys of the player are read from stdin
+        return Err("--input - requires --dump".to_string());
+    }
+
+    Ok(VisualizeOptions {
+        day: day.ok_or("missing day")?,
+        input,
+        fps: fps.unwrap_or(DEFAULT_FPS),
+        dump,
+        frames,
+    })
+}
+
+/// Parses the options for exporting an image
+fn parse_export(args: impl IntoIterator<Item = String>) -> Result<ExportOptions, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut output = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+
+        match name.as_str() {
+            "--input" | "--output" => {
+                let value = value.or_else(|| args.next()).ok_or_else(|| format!("missing value for {}", name))?;
+                match name.as_str() {
+                    "--input" => input = Some(if value == "-" { InputSource::Stdin } else { InputSource::File(PathBuf::from(value)) }),
+                    _ => output = Some(PathBuf::from(value)),
+                }
+            }
+            _ if name.starts_with("--") => return Err(format!("unknown option '{}'", name)),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(ExportOptions {
+        day: day.ok_or("missing day")?,
+        input,
+        output,
+    })
+}
+
+/// Parses the options for solving days
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut days = None;
+    let mut part = None;
+    let mut input = None;
+    let mut bench = false;
+    let mut verify = false;
+    let mut format = None;
+    let mut runs = None;
+    let mut report = None;
+    let mut parallel = false;
+    let mut jobs = None;
+    let mut timeout = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+
+        match name.as_str() {
+            "--part" | "--input" | "--runs" | "--report" | "--format" | "--jobs" | "--timeout" => {
+                let value = value.or_else(|| args.next()).ok_or_else(|| format!("missing value for {}", name))?;
+                match name.as_str() {
+                    "--part" => part = Some(value.parse()?),
+                    "--input" => input = Some(if value == "-" { InputSource::Stdin } else { InputSource::File(PathBuf::from(value)) }),
+                    "--format" => format = Some(value.parse::<OutputFormat>()?),
+                    "--runs" => runs = Some(value.parse::<usize>().ok().filter(|r| *r > 0).ok_or_else(|| format!("invalid number of runs '{}'", value))?),
+                    "--jobs" => jobs = Some(value.parse::<usize>().ok().filter(|j| *j > 0).ok_or_else(|| format!("invalid number of jobs '{}'", value))?),
+                    "--timeout" => {
+                        let seconds = value.parse::<f64>().ok().filter(|t| t.is_finite() && *t > 0.0).ok_or_else(|| format!("invalid timeout '{}'", value))?;
+                        timeout = Some(Duration::from_secs_f64(seconds));
+                    }
+                    _ => report = Some(PathBuf::from(value)),
+                }
+            }
+            "--bench" => bench = true,
+            "--verify" => verify = true,
+            "--parallel" => parallel = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if name.starts_with("--") => return Err(format!("unknown option '{}'", name)),
+            _ if days.is_none() => days = Some(arg.parse()?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let days = days.ok_or("missing day selection")?;
+    if input.is_some() && !matches!(&days, DaySelection::Range(r) if r.start() == r.end()) {
+        return Err("--input can only be used with a single day".to_string());
+    }
+
+    let bench = match (bench, runs.is_some() || report.is_some()) {
+        (true, _) if part.is_some() => return Err("--part cannot be used with --bench".to_string()),
+        (true, _) if verify => return Err("--verify cannot be used with --bench".to_string()),
+        (true, _) => Some(BenchOptions {
+            runs: runs.unwrap_or(DEFAULT_RUNS),
+            report,
+        }),
+        (false, true) => return Err("--runs and --report require --bench".to_string()),
+        (false, false) => None,
+    };
+
+    let parallel = match (parallel, jobs) {
+        (true, _) if bench.is_some() || verify => return Err("--parallel cannot be used with --bench or --verify".to_string()),
+        (true, jobs) => Some(ParallelOptions {
+            jobs: jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get())),
+        }),
+        (false, Some(_)) => return Err("--jobs requires --parallel".to_string()),
+        (false, None) => None,
+    };
+
+    if timeout.is_some() && (bench.is_some() || verify) {
+        return Err("--timeout cannot be used with --bench or --verify".to_string());
+    }
+
+    let format = format.unwrap_or_else(OutputFormat::detect);
+    if (bench.is_some() || verify || parallel.is_some()) && !format.is_human() {
+        return Err("--bench, --verify and --parallel only support the human and plain formats".to_string());
+    }
+
+    Ok(Options {
+        days,
+        part,
+        input,
+        bench,
+        parallel,
+        timeout,
+        format,
+        verify,
+    })
+}
+
+fn main() {
+    let (args, trace) = split_trace_args(env::args().skip(1).collect());
+    if let Some(output) = trace {
+        init_tracing(output).unwrap_or_else(|e| exit_with_error(&e));
+    }
+
+    let command = parse_command(args).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        process::exit(2)
+    });
+
+    let options = match command {
+        Command::Run(options) => options,
+        Command::New(day) => {
+            let root = find_workspace_root().unwrap_or_else(|| exit_with_error(&io::Error::new(io::ErrorKind::NotFound, "workspace root not found")));
+            match scaffold::new_day(&root, day) {
+                Ok(files) => files.iter().for_each(|f| println!("{}", f.strip_prefix(&root).unwrap_or(f).display())),
+                Err(e) => exit_with_error(&e),
+            }
+
+            return;
+        }
+        Command::Fetch(days) => {
+            if run_fetch(&select_solutions(&days)) {
+                process::exit(1);
+            }
+
+            return;
+        }
+        Command::Submit(day, part) => {
+            if run_submit(&select_solutions(&DaySelection::Range(day..=day)), part) {
+                process::exit(1);
+            }
+
+            return;
+        }
+        Command::Watch(day) => run_watch(select_solutions(&DaySelection::Range(day..=day))[0].day()),
+        Command::Gen(options) => {
+            run_gen(&options);
+            return;
+        }
+        Command::Visualize(options) => {
+            run_visualize(options);
+            return;
+        }
+        Command::Export(options) => {
+            run_export(options);
+            return;
+        }
+    };
+
+    let solutions = select_solutions(&options.days);
+
+    // Colors are only written in the human format, also when stdout is not a terminal
+    set_color_enabled(options.format == OutputFormat::Human);
+    let failed = match &options.bench {
+        Some(bench) => run_bench(&solutions, options.input, bench),
+        None if options.parallel.is_some() => run_in_parallel(&solutions, options.input, options.part, options.parallel.as_ref().unwrap(), options.timeout),
+        None if options.verify => {
+            let expected = ExpectedAnswers::load().unwrap_or_else(|e| exit_with_error(&e));
+            run_verify(&solutions, options.input, options.part, &expected)
+        }
+        None => run(&solutions, options.input, options.part, options.timeout, options.format),
+    };
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Solutions of the selected days, exits if there are none
+fn select_solutions(days: &DaySelection) -> Vec<&'static &'static dyn DynSolution> {
+    let solutions: Vec<_> = SOLUTIONS.iter().filter(|s| days.contains(s.day())).collect();
+    if solutions.is_empty() {
+        eprintln!("No solution for the selected day(s)");
+        process::exit(2);
+    }
+
+    solutions
+}
+
+/// Downloads the inputs of the given days unless they already exist, returns `true` if at least one download failed
+fn run_fetch(solutions: &[&&dyn DynSolution]) -> bool {
+    let client = AocClient::from_env().unwrap_or_else(|e| exit_with_error(&e));
+    let mut failed = false;
+    for solution in solutions {
+        let path = get_input_file_name(solution.day());
+        match client.ensure_input_at(solution.day(), &path) {
+            Ok(true) => println!("Day {}: downloaded {}", solution.day(), path.display()),
+            Ok(false) => println!("Day {}: cached {}", solution.day(), path.display()),
+            Err(e) => {
+                eprintln!("Day {}: {}", solution.day(), e);
+                failed = true;
+            }
+        }
+    }
+
+    failed
+}
+
+/// Solves a part of the first given day and submits the answer, returns `true` unless the answer is correct
+fn run_submit(solutions: &[&&dyn DynSolution], part: Part) -> bool {
+    let solution = solutions[0];
+    if part == Part::Two && !solution.has_part2() {
+        eprintln!("Day {} has no star 2", solution.day());
+        return true;
+    }
+
+    let input = InputSource::File(get_input_file_name(solution.day()));
+    let answers = try_read_input(input.clone())
+        .and_then(|content| solution.solve(&content, Some(part)).map_err(|e| InputError::Parse(input, e)))
+        .unwrap_or_else(|e| exit_with_error(&e));
+    let answer = answers.iter().next().map(|(_, a)| a.to_string()).expect("solved part has an answer");
+    println!("Day {}, star {}: {}", solution.day(), part.number(), answer);
+
+    let client = AocClient::from_env().unwrap_or_else(|e| exit_with_error(&e));
+    let mut log = SubmissionLog::load().unwrap_or_else(|e| exit_with_error(&e));
+    let attempt = client.submit(&mut log, solution.day(), part, &answer).unwrap_or_else(|e| exit_with_error(&e));
+    match attempt.wait_seconds {
+        Some(wait) => println!("Answer is {} (wait {}s before the next submission)", attempt.outcome, wait),
+        None => println!("Answer is {}", attempt.outcome),
+    }
+
+    attempt.outcome != Outcome::Correct
+}
+
+/// Rebuilds and solves a day whenever its sources or input change and compares the answers with the previous run and the known answers
+fn run_watch(day: i32) -> ! {
+    let root = find_workspace_root().unwrap_or_else(|| exit_with_error(&io::Error::new(io::ErrorKind::NotFound, "workspace root not found")));
+    let mut watcher = Watcher::new(watched_paths(&root, day));
+    let mut previous = None;
+    loop {
+        print_day_header(day);
+        // Reloaded on every run, answers.toml may have been updated in the meantime
+        let expected = ExpectedAnswers::load().unwrap_or_else(|e| {
+            eprintln!("  Warning: {}", e);
+            ExpectedAnswers::default()
+        });
+
+        match run_day_binary(&root, day) {
+            Ok(answers) => {
+                write_changes(&mut io::stdout(), previous.as_ref(), &answers, expected.day(day)).expect("failed to write output");
+                previous = Some(answers);
+            }
+            Err(e) => eprintln!("  Error: {}", e),
+        }
+
+        println!("Watching day{:0>2}/src and the input for changes (Ctrl-C to stop)", day);
+        watcher.wait(WATCH_INTERVAL);
+    }
+}
+
+/// Prints a random input for a day, the seed is written to stderr if it was not given so that the input can be reproduced
+fn run_gen(options: &GenOptions) {
+    let Some(generator) = GENERATORS.iter().find(|g| g.day == options.day) else {
+        let days: Vec<_> = GENERATORS.iter().map(|g| g.day.to_string()).collect();
+        eprintln!("No input generator for day {} (available: {})", options.day, days.join(", "));
+        process::exit(2);
+    };
+
+    let seed = options.seed.unwrap_or_else(|| {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+        eprintln!("Seed: {}", seed);
+        seed
+    });
+
+    println!("{}", generator.generate(seed, options.size));
+}
+
+/// Plays the animation of a day in the terminal or writes its frames to a directory
+fn run_visualize(options: VisualizeOptions) {
+    let Some(visualization) = VISUALIZATIONS.iter().find(|v| v.day == options.day) else {
+        eprintln!("No visualization for day {}, available:", options.day);
+        VISUALIZATIONS.iter().for_each(|v| eprintln!("  {:>2}  {}", v.day, v.description));
+        process::exit(2);
+    };
+
+    if options.dump.is_none() && !io::stdout().is_terminal() {
+        eprintln!("Standard output is not a terminal, use --dump <dir> to write the frames to files");
+        process::exit(2);
+    }
+
+    let input = options.input.unwrap_or_else(|| InputSource::File(get_input_file_name(options.day)));
+    let content = try_read_input(input.clone()).unwrap_or_else(|e| exit_with_error(&e));
+    let mut animation = (visualization.create)(&normalize_input(&content)).unwrap_or_else(|e| exit_with_error(&InputError::Parse(input, e)));
+    match options.dump {
+        Some(dir) => match dump_frames(animation.as_mut(), &dir, options.frames) {
+            Ok(count) => println!("Wrote {} frames to {}", count, dir.display()),
+            Err(e) => exit_with_error(&e),
+        },
+        None => play(animation.as_mut(), options.fps).unwrap_or_else(|e| exit_with_error(&e)),
+    }
+}
+
+/// Writes the image of a day and prints its path
+fn run_export(options: ExportOptions) {
+    let Some(export) = EXPORTS.iter().find(|e| e.day == options.day) else {
+        eprintln!("No image export for day {}, available:", options.day);
+        EXPORTS.iter().for_each(|e| eprintln!("  {:>2}  {} ({})", e.day, e.description, e.extension));
+        process::exit(2);
+    };
+
+    let input = options.input.unwrap_or_else(|| InputSource::File(get_input_file_name(options.day)));
+    let output = options.output.unwrap_or_else(|| export.default_path(&input));
+    let content = try_read_input(input.clone()).unwrap_or_else(|e| exit_with_error(&e));
+    let image = (export.render)(&normalize_input(&content)).unwrap_or_else(|e| exit_with_error(&InputError::Parse(input, e)));
+    fs::write(&output, image).unwrap_or_else(|e| exit_with_error(&e));
+    println!("{}", output.display());
+}
+
+/// Solves the given days, returns `true` if at least one day failed
+fn run(solutions: &[&&dyn DynSolution], input: Option<InputSource>, part: Option<Part>, timeout: Option<Duration>, format: OutputFormat) -> bool {
+    let mut printer = ResultPrinter::stdout(format);
+    let mut failed = false;
+    for solution in solutions {
+        if part == Some(Part::Two) && !solution.has_part2() {
+            if format.is_human() {
+                print_day_header(solution.day());
+                println!("  No star 2");
+            }
+
+            continue;
+        }
+
+        let input = input.clone().unwrap_or_else(|| InputSource::File(get_input_file_name(solution.day())));
+        if let Err(e) = run_solution(**solution, input, part, timeout, &mut printer) {
+            // Keep going with the other days, but report the failure in the exit code
+            match e {
+                // Same message as in the summary of `--parallel`
+                RunError::TimedOut { .. } => eprintln!("Day {} {}", solution.day(), e),
+                _ if format.is_human() => eprintln!("  Error: {}", e),
+                _ => eprintln!("Day {}: {}", solution.day(), e),
+            }
+
+            failed = true;
+        }
+    }
+
+    failed
+}
+
+/// Solves the given days concurrently and prints a summary table, returns `true` if at least one day did not finish successfully
+fn run_in_parallel(solutions: &[&&'static dyn DynSolution], input: Option<InputSource>, part: Option<Part>, options: &ParallelOptions, timeout: Option<Duration>) -> bool {
+    let jobs = solutions
+        .iter()
+        .map(|solution| (**solution, input.clone().unwrap_or_else(|| InputSource::File(get_input_file_name(solution.day())))))
+        .collect();
+    let start = Instant::now();
+    let reports = run_parallel(jobs, part, options.jobs, timeout);
+    write_summary(&mut io::stdout(), &reports).expect("failed to write output");
+    println!("Finished in {:.2?} (jobs: {})", start.elapsed(), options.jobs);
+    reports.iter().any(|r| r.status != DayStatus::Ok)
+}
+
+/// Solves the given days and compares the answers with the known answers
+///
+/// Returns `true` if at least one answer is wrong or a day failed. Missing known answers are reported, but do not fail.
+fn run_verify(solutions: &[&&dyn DynSolution], input: Option<InputSource>, part: Option<Part>, expected: &ExpectedAnswers) -> bool {
+    let mut failed = false;
+    let (mut pass, mut fail, mut missing) = (0, 0, 0);
+    for solution in solutions {
+        let input = input.clone().unwrap_or_else(|| InputSource::File(get_input_file_name(solution.day())));
+        match verify_solution(**solution, input, part, expected.day(solution.day())) {
+            Ok(verdicts) => {
+                for (_, verdict) in verdicts {
+                    match verdict {
+                        Verdict::Pass(_) => pass += 1,
+                        Verdict::Fail { .. } => fail += 1,
+                        Verdict::Missing(_) => missing += 1,
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("  Error: {}", e);
+                failed = true;
+            }
+        }
+    }
+
+    println!("\n{} passed, {} failed, {} missing", pass, fail, missing);
+    failed || fail > 0
+}
+
+/// Benchmarks the given days and writes the JSON report, returns `true` if at least one day failed
+fn run_bench(solutions: &[&&dyn DynSolution], input: Option<InputSource>, options: &BenchOptions) -> bool {
+    let mut failed = false;
+    let mut report = BenchReport::default();
+    for solution in solutions {
+        let input = input.clone().unwrap_or_else(|| InputSource::File(get_input_file_name(solution.day())));
+        match try_read_input(input.clone()).and_then(|content| bench_solution(**solution, &content, options.runs).map_err(|e| InputError::Parse(input, e))) {
+            Ok(bench) => {
+                print_bench(&bench);
+                report.days.push(bench);
+            }
+            Err(e) => {
+                print_day_header(solution.day());
+                eprintln!("  Error: {}", e);
+                failed = true;
+            }
+        }
+    }
+
+    match &options.report {
+        Some(path) if path.as_os_str() == "-" => println!("{}", report.to_json()),
+        Some(path) => {
+            if let Err(e) = fs::write(path, report.to_json()) {
+                eprintln!("Cannot write report {}: {}", path.display(), e);
+                failed = true;
+            }
+        }
+        None => {}
+    }
+
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn day_apis_are_public() {
+        assert_eq!(2021, day16::Packet::decode("D2FE28").unwrap().evaluate());
+        assert_eq!(101 * 101 * 101, day22::initialization_area().volume());
+        assert_eq!(3, day19::v3!(1, -1, 1).manhattan_distance(&day19::Vector3d::default()));
+    }
+
+    #[test]
+    fn solutions_ordered_by_day() {
+        assert!(SOLUTIONS.windows(2).all(|w| w[0].day() < w[1].day()));
+        assert!(GENERATORS.windows(2).all(|w| w[0].day < w[1].day));
+        assert!(VISUALIZATIONS.windows(2).all(|w| w[0].day < w[1].day));
+        assert!(EXPORTS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+
+    #[test]
+    fn parse_single_day() {
+        let options = parse_args(args(&["16", "--part", "2"])).unwrap();
+        assert_eq!(DaySelection::Range(16..=16), options.days);
+        assert_eq!(Some(Part::Two), options.part);
+        assert_eq!(None, options.input);
+        assert_eq!(None, options.bench);
+        assert!(!options.verify);
+    }
+
+    #[test]
+    fn parse_format() {
+        let options = parse_args(args(&["all", "--format", "csv"])).unwrap();
+        assert_eq!(OutputFormat::Csv, options.format);
+        assert!(parse_args(args(&["all", "--format", "xml"])).is_err());
+        assert!(parse_args(args(&["all", "--format=json", "--verify"])).is_err());
+        assert!(parse_args(args(&["all", "--format=plain", "--bench"])).is_ok());
+    }
+
+    #[test]
+    fn parse_verify() {
+        let options = parse_args(args(&["all", "--verify"])).unwrap();
+        assert!(options.verify);
+        assert!(parse_args(args(&["all", "--verify", "--bench"])).is_err());
+    }
+
+    #[test]
+    fn parse_bench() {
+        let options = parse_args(args(&["all", "--bench"])).unwrap();
+        assert_eq!(Some(BenchOptions { runs: DEFAULT_RUNS, report: None }), options.bench);
+        let options = parse_args(args(&["19..=25", "--bench", "--runs", "3", "--report=bench.json"])).unwrap();
+        assert_eq!(
+            Some(BenchOptions {
+                runs: 3,
+                report: Some(PathBuf::from("bench.json"))
+            }),
+            options.bench
+        );
+    }
+
+    #[test]
+    fn parse_parallel() {
+        let options = parse_args(args(&["all", "--parallel", "--jobs", "4", "--timeout=2.5"])).unwrap();
+        assert_eq!(Some(ParallelOptions { jobs: 4 }), options.parallel);
+        assert_eq!(Some(Duration::from_millis(2500)), options.timeout);
+        assert!(parse_args(args(&["all", "--parallel"])).unwrap().parallel.is_some_and(|p| p.jobs > 0));
+        assert!(parse_args(args(&["all", "--jobs", "4"])).is_err());
+        assert!(parse_args(args(&["all", "--parallel", "--timeout", "0"])).is_err());
+        assert!(parse_args(args(&["all", "--parallel", "--verify"])).is_err());
+        assert!(parse_args(args(&["all", "--parallel", "--format", "csv"])).is_err());
+    }
+
+    #[test]
+    fn parse_timeout() {
+        let options = parse_args(args(&["23", "--timeout", "10"])).unwrap();
+        assert_eq!((Some(Duration::from_secs(10)), None), (options.timeout, options.parallel));
+        assert!(parse_args(args(&["all", "--timeout", "x"])).is_err());
+        assert!(parse_args(args(&["all", "--timeout", "10", "--verify"])).is_err());
+        assert!(parse_args(args(&["all", "--timeout", "10", "--bench"])).is_err());
+    }
+
+    #[test]
+    fn parse_input() {
+        let options = parse_args(args(&["--input=other.txt", "1"])).unwrap();
+        assert_eq!(Some(InputSource::File(PathBuf::from("other.txt"))), options.input);
+        let options = parse_args(args(&["1", "--input", "-"])).unwrap();
+        assert_eq!(Some(InputSource::Stdin), options.input);
+    }
+
+    #[test]
+    fn parse_new() {
+        assert!(matches!(parse_command(args(&["new", "8"])), Ok(Command::New(8))));
+        assert!(matches!(parse_command(args(&["8"])), Ok(Command::Run(_))));
+        assert!(parse_command(args(&["new"])).is_err());
+        assert!(parse_command(args(&["new", "26"])).is_err());
+        assert!(parse_command(args(&["new", "8", "9"])).is_err());
+    }
+
+    #[test]
+    fn parse_fetch() {
+        assert!(matches!(parse_command(args(&["fetch", "all"])), Ok(Command::Fetch(DaySelection::All))));
+        assert!(parse_command(args(&["fetch"])).is_err());
+        assert!(parse_command(args(&["fetch", "0"])).is_err());
+    }
+
+    #[test]
+    fn parse_submit() {
+        assert!(matches!(parse_command(args(&["submit", "7", "2"])), Ok(Command::Submit(7, Part::Two))));
+        assert!(parse_command(args(&["submit", "7"])).is_err());
+        assert!(parse_command(args(&["submit", "7", "3"])).is_err());
+        assert!(parse_command(args(&["submit", "all", "1"])).is_err());
+        assert!(parse_command(args(&["submit", "7", "1", "x"])).is_err());
+    }
+
+    #[test]
+    fn parse_watch() {
+        assert!(matches!(parse_command(args(&["watch", "16"])), Ok(Command::Watch(16))));
+        assert!(parse_command(args(&["watch"])).is_err());
+        assert!(parse_command(args(&["watch", "1..=2"])).is_err());
+        assert!(parse_command(args(&["watch", "16", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn parse_gen() {
+        let Ok(Command::Gen(options)) = parse_command(args(&["gen", "19", "--seed", "42", "--size=5"])) else {
+            panic!("expected gen command");
+        };
+        assert_eq!(
+            GenOptions {
+                day: 19,
+                seed: Some(42),
+                size: Some(5)
+            },
+            options
+        );
+        assert!(matches!(parse_command(args(&["gen", "1"])), Ok(Command::Gen(GenOptions { day: 1, seed: None, size: None }))));
+        assert!(parse_command(args(&["gen"])).is_err());
+        assert!(parse_command(args(&["gen", "1", "--size", "0"])).is_err());
+        assert!(parse_command(args(&["gen", "1", "--seed", "-1"])).is_err());
+        assert!(parse_command(args(&["gen", "1", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn parse_visualize() {
+        let Ok(Command::Visualize(options)) = parse_command(args(&["visualize", "25", "--dump", "frames", "--frames=20", "--input", "x.txt"])) else {
+            panic!("expected visualize command");
+        };
+        assert_eq!(
+            VisualizeOptions {
+                day: 25,
+                input: Some(InputSource::File(PathBuf::from("x.txt"))),
+                fps: DEFAULT_FPS,
+                dump: Some(PathBuf::from("frames")),
+                frames: Some(20)
+            },
+            options
+        );
+
+        let Ok(Command::Visualize(options)) = parse_command(args(&["visualize", "5", "--fps", "2.5"])) else {
+            panic!("expected visualize command");
+        };
+        assert_eq!((5, 2.5, None), (options.day, options.fps, options.dump));
+        assert!(parse_command(args(&["visualize"])).is_err());
+        assert!(parse_command(args(&["visualize", "5", "--fps", "0"])).is_err());
+        assert!(parse_command(args(&["visualize", "5", "--frames", "3"])).is_err());
+        assert!(parse_command(args(&["visualize", "5", "--input", "-"])).is_err());
+        assert!(parse_command(args(&["visualize", "5", "--dump", "d", "--fps", "3"])).is_err());
+        assert!(parse_command(args(&["visualize", "5", "--part", "1"])).is_err());
+    }
+
+    #[test]
+    fn parse_export() {
+        let Ok(Command::Export(options)) = parse_command(args(&["export", "17", "--input", "-", "--output=trick.svg"])) else {
+            panic!("expected export command");
+        };
+        assert_eq!(
+            ExportOptions {
+                day: 17,
+                input: Some(InputSource::Stdin),
+                output: Some(PathBuf::from("trick.svg"))
+            },
+            options
+        );
+        assert!(matches!(
+            parse_command(args(&["export", "20"])),
+            Ok(Command::Export(ExportOptions { day: 20, input: None, output: None }))
+        ));
+        assert!(parse_command(args(&["export"])).is_err());
+        assert!(parse_command(args(&["export", "20", "--output"])).is_err());
+        assert!(parse_command(args(&["export", "20", "--fps", "1"])).is_err());
+    }
+
+    #[test]
+    fn exports_of_examples() {
+        for export in EXPORTS {
+            let example = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("../day{:0>2}/examples/example.txt", export.day));
+            let image = (export.render)(&fs::read_to_string(example).unwrap()).unwrap();
+            let header = match export.extension {
+                "pbm" => "P4\n",
+                "pgm" => "P5\n",
+                "ppm" => "P6\n",
+                _ => "<svg ",
+            };
+            assert!(image.starts_with(header.as_bytes()), "day {}", export.day);
+        }
+    }
+
+    #[test]
+    fn visualizations_of_examples() {
+        // Day 23 has no example file, its animation is tested in its crate
+        for visualization in VISUALIZATIONS.iter().filter(|v| v.day != 23) {
+            let example = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("../day{:0>2}/examples/example.txt", visualization.day));
+            let mut animation = (visualization.create)(&fs::read_to_string(example).unwrap()).unwrap();
+            let first = animation.frame();
+            assert!(!first.lines.is_empty(), "day {}", visualization.day);
+            assert!(animation.step(), "day {}", visualization.day);
+            assert_ne!(first, animation.frame(), "day {}", visualization.day);
+        }
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for generator in GENERATORS {
+            let solution = SOLUTIONS.iter().find(|s| s.day() == generator.day).unwrap();
+            let size = if generator.day == 19 { 3 } else { 10 };
+            assert!(solution.solve(&generator.generate(1, Some(size)), None).is_ok(), "day {}", generator.day);
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_args(args(&[])).is_err());
+        assert!(parse_args(args(&["all", "--input", "x.txt"])).is_err());
+        assert!(parse_args(args(&["1", "--part"])).is_err());
+        assert!(parse_args(args(&["1", "--part", "3"])).is_err());
+        assert!(parse_args(args(&["1", "2"])).is_err());
+        assert!(parse_args(args(&["1", "--verbose"])).is_err());
+        assert!(parse_args(args(&["1", "--runs", "3"])).is_err());
+        assert!(parse_args(args(&["1", "--bench", "--runs", "0"])).is_err());
+        assert!(parse_args(args(&["1", "--bench", "--part", "1"])).is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Files of a new day crate (relative to the crate directory) and their templates
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("examples/answers.toml", include_str!("../templates/answers.toml.tmpl")),
+];
+
+/// Errors that can occur while generating a new day
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// Day is not between 1 and 25
+    InvalidDay(i32),
+    /// The day already has a crate
+    Exists(PathBuf),
+    /// A file cannot be read or written
+    Io(PathBuf, io::Error),
+    /// A file that has to be updated does not contain the expected section
+    Layout(PathBuf, &'static str),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "invalid day {}, expected a number between 1 and 25", day),
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Io(path, err) => write!(f, "cannot access {}: {}", path.display(), err),
+            ScaffoldError::Layout(path, section) => write!(f, "cannot register new day in {}: {} not found", path.display(), section),
+        }
+    }
+}
+
+impl Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScaffoldError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Generates the crate `dayNN` in the given workspace and registers it
+///
+/// Besides the crate itself, the day is added to the workspace members, to the dependencies of the
+/// runner and to its list of solutions. Existing files (e.g. the Go solutions of days 8 to 15) are
+/// never overwritten. Returns the created and updated files.
+pub fn new_day(root: &Path, day: i32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+
+    let name = format!("day{:0>2}", day);
+    let dir = root.join(&name);
+    let manifest = dir.join("Cargo.toml");
+    if manifest.exists() {
+        return Err(ScaffoldError::Exists(manifest));
+    }
+
+    // Check the files to update before writing anything, so that a failure leaves no half-registered day
+    let updates = [
+        (root.join("Cargo.toml"), "members = [", "]", format!("    \"{}\",", name)),
+        (root.join("aoc").join("Cargo.toml"), "[dependencies]", "[", format!("{} = {{ path = \"../{}\" }}", name, name)),
+        (root.join("aoc").join("src").join("main.rs"), "const SOLUTIONS", "];", format!("    &{}::Day{:0>2},", name, day)),
+    ];
+    let updated = updates
+        .iter()
+        .map(|(path, start, end, line)| {
+            let content = fs::read_to_string(path).map_err(|e| ScaffoldError::Io(path.clone(), e))?;
+            insert_sorted(&content, start, end, line).ok_or(ScaffoldError::Layout(path.clone(), start))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut files = Vec::new();
+    for (file, template) in TEMPLATES {
+        let path = dir.join(file);
+        let content = template
+            .replace("{{crate}}", &name)
+            .replace("{{type}}", &format!("Day{:0>2}", day))
+            .replace("{{day}}", &day.to_string());
+        create_file(&path, &content)?;
+        files.push(path);
+    }
+
+    for ((path, ..), content) in updates.into_iter().zip(updated) {
+        fs::write(&path, content).map_err(|e| ScaffoldError::Io(path.clone(), e))?;
+        files.push(path);
+    }
+
+    Ok(files)
+}
+
+fn create_file(path: &Path, content: &str) -> Result<(), ScaffoldError> {
+    let io_err = |e| ScaffoldError::Io(path.to_path_buf(), e);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_err)?;
+    }
+
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::AlreadyExists => ScaffoldError::Exists(path.to_path_buf()),
+        _ => io_err(e),
+    })?;
+    file.write_all(content.as_bytes()).map_err(io_err)
+}
+
+/// Inserts a line into a sorted block of lines
+///
+/// The block starts after the first line beginning with `start` and ends before the next line beginning
+/// with `end` (or at the end of the content). Returns `None` if the block does not exist.
+fn insert_sorted(content: &str, start: &str, end: &str, line: &str) -> Option<String> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let first = lines.iter().position(|l| l.trim_start().starts_with(start))? + 1;
+    let block_end = lines[first..].iter().position(|l| l.trim_start().starts_with(end)).map_or(lines.len(), |ix| first + ix);
+
+    // Skip trailing empty lines of the block so that the new line is placed next to its siblings
+    let last = (first..block_end).rev().find(|ix| !lines[*ix].trim().is_empty()).map_or(first, |ix| ix + 1);
+    if !lines[first..last].contains(&line) {
+        let ix = (first..last).find(|ix| !lines[*ix].trim().is_empty() && lines[*ix].trim() > line.trim()).unwrap_or(last);
+        lines.insert(ix, line);
+    }
+
+    let mut result = lines.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn insert_into_block() {
+        let content = "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day16\",\n]\n";
+        let result = insert_sorted(content, "members = [", "]", "    \"day08\",").unwrap();
+        assert_eq!("[workspace]\n\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day08\",\n    \"day16\",\n]\n", result);
+        assert_eq!(result, insert_sorted(&result, "members = [", "]", "    \"day08\",").unwrap());
+        assert_eq!(None, insert_sorted(content, "[dependencies]", "[", "day08 = 1"));
+    }
+
+    #[test]
+    fn insert_at_end_of_file() {
+        let result = insert_sorted("[dependencies]\naoc_utils = 1\nday01 = 1\n", "[dependencies]", "[", "day08 = 1").unwrap();
+        assert_eq!("[dependencies]\naoc_utils = 1\nday01 = 1\nday08 = 1\n", result);
+    }
+
+    #[test]
+    fn generate_day() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::create_dir_all(root.join("day08")).unwrap();
+        fs::write(root.join("day08/input.txt"), "go input").unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"day01\",\n]\n").unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), "[dependencies]\nday01 = { path = \"../day01\" }\n").unwrap();
+        fs::write(root.join("aoc/src/main.rs"), "const SOLUTIONS: &[&dyn DynSolution] = &[\n    &day01::Day01,\n];\n").unwrap();
+
+        let files = new_day(&root, 8).unwrap();
+        assert_eq!(7, files.len());
+        assert_eq!("go input", fs::read_to_string(root.join("day08/input.txt")).unwrap());
+        let lib = fs::read_to_string(root.join("day08/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day08;") && lib.contains("const DAY: i32 = 8;") && lib.contains("mod tests_star1"));
+        assert!(fs::read_to_string(root.join("day08/src/main.rs")).unwrap().contains("use day08::Day08;"));
+        assert!(fs::read_to_string(root.join("Cargo.toml")).unwrap().contains("    \"day01\",\n    \"day08\",\n]"));
+        assert!(fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap().ends_with("day08 = { path = \"../day08\" }\n"));
+        assert!(fs::read_to_string(root.join("aoc/src/main.rs")).unwrap().contains("    &day01::Day01,\n    &day08::Day08,\n];"));
+
+        assert!(matches!(new_day(&root, 8), Err(ScaffoldError::Exists(_))));
+        assert!(matches!(new_day(&root, 26), Err(ScaffoldError::InvalidDay(26))));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+//! Procedural macros for the solutions, re-exported by `aoc_utils`
+
+use std::{collections::BTreeMap, env, fs, path::Path};
+
+use proc_macro::TokenStream;
+use serde::Deserialize;
+
+/// Directory with the examples, relative to the crate of the day (`aoc_utils::EXAMPLES_DIR`)
+const EXAMPLES_DIR: &str = "examples";
+
+/// Sidecar file with the expected answers (`aoc_utils::EXAMPLE_ANSWERS_FILE`)
+const ANSWERS_FILE: &str = "answers.toml";
+
+/// Expected answers of an example, same format as in the workspace's `answers.toml`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExampleAnswers {
+    star1: Option<String>,
+    star2: Option<String>,
+}
+
+/// Generates a `#[test]` per example and part for the given solution
+///
+/// Every `examples/<name>.txt` file of the crate is an example input. Its expected answers are read from
+/// the section `[<name>]` in `examples/answers.toml`; a test `<name>_star1` and/or `<name>_star2` is
+/// generated for each answer that is given.
+///
+/// ```ignore
+/// aoc_utils::example_tests!(Day02);
+/// ```
+#[proc_macro]
+pub fn example_tests(input: TokenStream) -> TokenStream {
+    let solution = input.to_string();
+    let code = match generate(solution.trim()) {
+        Ok(code) => code,
+        Err(message) => format!("compile_error!({:?});", message),
+    };
+
+    code.parse().expect("generated code is valid")
+}
+
+fn generate(solution: &str) -> Result<String, String> {
+    if solution.is_empty() {
+        return Err("expected the solution type, e.g. `example_tests!(Day02)`".to_string());
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").map_err(|_| "CARGO_MANIFEST_DIR is not set".to_string())?;
+    let dir = Path::new(&manifest_dir).join(EXAMPLES_DIR);
+    let answers_path = dir.join(ANSWERS_FILE);
+    let answers = fs::read_to_string(&answers_path).map_err(|e| format!("cannot read {}: {}", answers_path.display(), e))?;
+    let mut answers: BTreeMap<String, ExampleAnswers> = toml::from_str(&answers).map_err(|e| format!("invalid {}: {}", answers_path.display(), e))?;
+
+    let mut examples: Vec<_> = fs::read_dir(&dir)
+        .map_err(|e| format!("cannot read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    examples.sort();
+
+    // Including the answers file makes cargo rebuild the tests when it changes (e.g. for a new example)
+    let mut code = format!("#[cfg(test)]\nmod examples {{\n    const _: &str = include_str!({:?});\n", answers_path.display().to_string());
+    for path in examples {
+        let name = path.file_stem().and_then(|s| s.to_str()).ok_or_else(|| format!("invalid example file name {}", path.display()))?;
+        let expected = answers.remove(name).ok_or_else(|| format!("no answers for example {}.txt in {}", name, answers_path.display()))?;
+        if expected.star1.is_none() && expected.star2.is_none() {
+            return Err(format!("no answers for example {}.txt in {}", name, answers_path.display()));
+        }
+
+        for (number, part, answer) in [(1, "One", expected.star1), (2, "Two", expected.star2)] {
+            if let Some(answer) = answer {
+                code += &format!(
+                    "\n    #[test]\n    fn {}_star{}() {{\n        ::aoc_utils::check_example::<super::{}>(include_str!({:?}), ::aoc_utils::Part::{}, {:?});\n    }}\n",
+                    test_name(name),
+                    number,
+                    solution,
+                    path.display().to_string(),
+                    part,
+                    answer
+                );
+            }
+        }
+    }
+
+    if let Some(name) = answers.keys().next() {
+        return Err(format!("{} contains answers for {}, but there is no example {}.txt", answers_path.display(), name, name));
+    }
+
+    code += "}\n";
+    Ok(code)
+}
+
+/// Converts an example file name into a valid test function name
+fn test_name(name: &str) -> String {
+    let name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("example_{}", name)
+    } else {
+        name
+    }
+}
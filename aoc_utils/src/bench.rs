@@ -0,0 +1,194 @@
+use std::{fmt, time::Duration};
+
+use serde::{Serialize, Serializer};
+
+use crate::{print_day_header, Allocations, DynSolution, ParseError, Timings};
+
+/// Minimum, median and maximum of a series of measurements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "max_ns", serialize_with = "as_nanos")]
+    pub max: Duration,
+}
+
+fn as_nanos<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_nanos().try_into().unwrap_or(u64::MAX))
+}
+
+impl Stats {
+    /// Calculates the statistics of the given samples
+    ///
+    /// Panics if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2 } else { sorted[mid] };
+
+        Stats {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "min {:>10.2?}  median {:>10.2?}  max {:>10.2?}", self.min, self.median, self.max)
+    }
+}
+
+/// Benchmark result of a single day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayBench {
+    pub day: i32,
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    /// `None` if the puzzle has no second part
+    pub part2: Option<Stats>,
+    /// Allocations of a single run, only if allocations are counted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<Allocations>,
+}
+
+/// Benchmark results of all selected days, serialized as JSON report
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BenchReport {
+    pub days: Vec<DayBench>,
+}
+
+impl BenchReport {
+    /// Converts the report into pretty printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report is always serializable")
+    }
+}
+
+/// Parses and solves the given input `runs` times, measuring parse, part 1 and part 2 separately
+///
+/// If allocations are counted, one additional run counts the allocations of each step. Fails if the input cannot be parsed. Panics if `runs` is zero.
+pub fn bench_solution(solution: &dyn DynSolution, input: &str, runs: usize) -> Result<DayBench, ParseError> {
+    assert!(runs > 0, "at least one run is required");
+
+    let timings = (0..runs).map(|_| solution.time(input)).collect::<Result<Vec<_>, _>>()?;
+    let samples = |f: fn(&Timings) -> Option<Duration>| timings.iter().filter_map(f).collect::<Vec<_>>();
+    let part2 = samples(|t| t.part2);
+
+    Ok(DayBench {
+        day: solution.day(),
+        runs,
+        parse: Stats::from_samples(&samples(|t| Some(t.parse))),
+        part1: Stats::from_samples(&samples(|t| Some(t.part1))),
+        part2: (!part2.is_empty()).then(|| Stats::from_samples(&part2)),
+        allocations: solution.allocations(input)?,
+    })
+}
+
+/// Prints the benchmark result of a day
+pub fn print_bench(bench: &DayBench) {
+    print_day_header(bench.day);
+    println!("  Parse:  {}", bench.parse);
+    println!("  Star 1: {}", bench.part1);
+    if let Some(part2) = &bench.part2 {
+        println!("  Star 2: {}", part2);
+    }
+
+    if let Some(allocations) = &bench.allocations {
+        println!("  Memory:");
+        println!("    Parse:  {}", allocations.parse);
+        println!("    Star 1: {}", allocations.part1);
+        if let Some(part2) = &allocations.part2 {
+            println!("    Star 2: {}", part2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{counting_allocations, NoAnswer, Solution};
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: i32 = 99;
+        const HAS_PART2: bool = false;
+        type Input = usize;
+        type Answer1 = usize;
+        type Answer2 = NoAnswer;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.lines().count())
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            *input
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            NoAnswer
+        }
+    }
+
+    fn ms(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn stats_odd_samples() {
+        let stats = Stats::from_samples(&ms(&[5, 1, 3]));
+        assert_eq!(
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5)
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn stats_even_samples() {
+        let stats = Stats::from_samples(&ms(&[4, 1, 2, 8]));
+        assert_eq!(Duration::from_millis(3), stats.median);
+    }
+
+    #[test]
+    fn bench_runs() {
+        let bench = bench_solution(&Lines, "a\nb", 3).unwrap();
+        assert_eq!(99, bench.day);
+        assert_eq!(3, bench.runs);
+        assert!(bench.parse.min <= bench.parse.median && bench.parse.median <= bench.parse.max);
+        assert_eq!(None, bench.part2);
+        assert_eq!(counting_allocations(), bench.allocations.is_some());
+    }
+
+    #[test]
+    fn report_json() {
+        let stats = Stats::from_samples(&ms(&[1]));
+        let report = BenchReport {
+            days: vec![DayBench {
+                day: 1,
+                runs: 1,
+                parse: stats,
+                part1: stats,
+                part2: None,
+                allocations: None,
+            }],
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(1, json["days"][0]["day"]);
+        assert_eq!(1_000_000, json["days"][0]["parse"]["median_ns"]);
+        assert!(json["days"][0]["part2"].is_null());
+        assert!(json["days"][0].get("allocations").is_none());
+    }
+}
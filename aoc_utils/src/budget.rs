@@ -0,0 +1,118 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Budget>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Default)]
+struct State {
+    cancelled: AtomicBool,
+    best: Mutex<Option<String>>,
+}
+
+/// Time budget and cancellation token for long-running solvers
+///
+/// The runner installs a budget for the thread that solves a day (see [`Budget::install`]). Solvers that
+/// may run for a long time get it with [`Budget::current`], check [`Budget::is_exhausted`] periodically
+/// and stop early once it returns `true`. The best result found so far can be reported with
+/// [`Budget::report_best`], so that the runner can show it if the day times out. Clones share the
+/// cancellation state and the reported result.
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    deadline: Option<Instant>,
+    state: Arc<State>,
+}
+
+impl Budget {
+    /// Budget without time limit that is only exhausted when cancelled
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Budget that is exhausted after the given time
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Budget {
+            deadline: Instant::now().checked_add(timeout),
+            state: Arc::default(),
+        }
+    }
+
+    /// Budget installed for the current thread, an unlimited budget if there is none
+    pub fn current() -> Self {
+        CURRENT.with(|current| current.borrow().clone()).unwrap_or_default()
+    }
+
+    /// Runs `f` with this budget installed as [`Budget::current`] for the current thread
+    pub fn install<T>(&self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Budget>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        // Restoring in drop also resets the budget if `f` panics
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+
+    /// Stops solvers using this budget (or a clone of it) at their next check
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the budget has been cancelled or its time is up
+    pub fn is_exhausted(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed) || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Records the best result found so far (replaces a previously reported result)
+    pub fn report_best(&self, value: impl fmt::Display) {
+        *self.state.best.lock().unwrap() = Some(value.to_string());
+    }
+
+    /// Best result reported so far
+    pub fn best(&self) -> Option<String> {
+        self.state.best.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout_and_cancel() {
+        let budget = Budget::with_timeout(Duration::from_secs(3600));
+        assert!(!budget.is_exhausted());
+        budget.clone().cancel();
+        assert!(budget.is_exhausted());
+
+        assert!(Budget::with_timeout(Duration::ZERO).is_exhausted());
+        assert!(!Budget::unlimited().is_exhausted());
+        assert!(!Budget::with_timeout(Duration::MAX).is_exhausted());
+    }
+
+    #[test]
+    fn current_budget() {
+        let budget = Budget::unlimited();
+        budget.install(|| {
+            Budget::current().report_best(42);
+            Budget::current().cancel();
+        });
+
+        assert_eq!(Some("42".to_string()), budget.best());
+        assert!(budget.is_exhausted());
+        assert!(!Budget::current().is_exhausted());
+        assert_eq!(None, Budget::current().best());
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::get_input_file_name;
+
+/// Year of the puzzles
+pub const YEAR: i32 = 2021;
+
+/// Environment variable with the session token (value of the `session` cookie of adventofcode.com)
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable that overrides [`DEFAULT_BASE_URL`] (e.g. for a local stub server)
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Server the inputs are downloaded from and the answers are submitted to
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub(crate) const USER_AGENT: &str = concat!("github.com/rstropek/AdventOfCode2021 aoc_utils/", env!("CARGO_PKG_VERSION"));
+
+/// Errors that can occur while downloading an input
+#[derive(Debug)]
+pub enum FetchError {
+    /// [`SESSION_ENV`] is not set
+    MissingSession,
+    /// Server responded with an error status code
+    Status { day: i32, status: u16 },
+    /// Server cannot be reached or the connection failed
+    Transport(String),
+    /// Downloaded input cannot be written to the cache
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => write!(f, "{} is not set, copy the value of the session cookie from adventofcode.com", SESSION_ENV),
+            FetchError::Status { day, status: 400 } => write!(f, "cannot download input of day {} (HTTP 400), session token is invalid or expired", day),
+            FetchError::Status { day, status: 404 } => write!(f, "cannot download input of day {} (HTTP 404), puzzle is not unlocked yet", day),
+            FetchError::Status { day, status } => write!(f, "cannot download input of day {} (HTTP {})", day, status),
+            FetchError::Transport(message) => write!(f, "cannot download input: {}", message),
+            FetchError::Io(path, err) => write!(f, "cannot write input file {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Client for adventofcode.com: downloads puzzle inputs and submits answers
+///
+/// Inputs are cached as input files. An input is only downloaded if its input file does not exist yet,
+/// so the server is contacted at most once per day.
+#[derive(Debug, Clone)]
+pub struct AocClient {
+    pub(crate) base_url: String,
+    pub(crate) session: String,
+}
+
+impl AocClient {
+    /// Creates a client for [`DEFAULT_BASE_URL`] with the given session token
+    pub fn new(session: impl Into<String>) -> Self {
+        AocClient {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Creates a client with the session token from [`SESSION_ENV`] and the base URL from [`BASE_URL_ENV`] (if set)
+    pub fn from_env() -> Result<Self, FetchError> {
+        let session = env::var(SESSION_ENV).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).ok_or(FetchError::MissingSession)?;
+        let client = Self::new(session);
+        Ok(match env::var(BASE_URL_ENV) {
+            Ok(url) if !url.is_empty() => client.with_base_url(url),
+            _ => client,
+        })
+    }
+
+    /// Changes the server the client talks to
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// URL of the input of a day
+    pub fn input_url(&self, day: i32) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, YEAR, day)
+    }
+
+    /// Downloads the input of a day without caching it
+    pub fn fetch(&self, day: i32) -> Result<String, FetchError> {
+        let response = ureq::get(&self.input_url(day))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(status, _) => FetchError::Status { day, status },
+                ureq::Error::Transport(t) => FetchError::Transport(t.to_string()),
+            })?;
+
+        response.into_string().map_err(|e| FetchError::Transport(e.to_string()))
+    }
+
+    /// Makes sure the input file of a day (see [`get_input_file_name`]) exists, downloading it if necessary
+    ///
+    /// Returns the path of the input file.
+    pub fn ensure_input(&self, day: i32) -> Result<PathBuf, FetchError> {
+        let path = get_input_file_name(day);
+        self.ensure_input_at(day, &path)?;
+        Ok(path)
+    }
+
+    /// Makes sure the given input file exists, downloading the input of the day if necessary
+    ///
+    /// Returns `true` if the input was downloaded, `false` if the file already existed.
+    pub fn ensure_input_at(&self, day: i32, path: &Path) -> Result<bool, FetchError> {
+        if path.exists() {
+            return Ok(false);
+        }
+
+        let input = self.fetch(day)?;
+        let io_err = |e| FetchError::Io(path.to_path_buf(), e);
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(io_err)?;
+        }
+
+        // Write to a temporary file first so that an interrupted write does not end up in the cache
+        let partial = path.with_extension("partial");
+        fs::write(&partial, input).map_err(io_err)?;
+        fs::rename(&partial, path).map_err(io_err)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::stub_server;
+    use std::net::TcpListener;
+
+    fn temp_input_file(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("day01").join("input.txt")
+    }
+
+    #[test]
+    fn download_and_cache() {
+        let (url, requests) = stub_server(200, "199\n200\n");
+        let client = AocClient::new("secret").with_base_url(format!("{}/", url));
+        let path = temp_input_file("cache");
+
+        assert!(client.ensure_input_at(1, &path).unwrap());
+        assert_eq!("199\n200\n", fs::read_to_string(&path).unwrap());
+        let request = requests.recv().unwrap();
+        assert_eq!("GET /2021/day/1/input HTTP/1.1", request.line);
+        assert_eq!("session=secret", request.cookie);
+
+        // Second call uses the cached file
+        assert!(!client.ensure_input_at(1, &path).unwrap());
+        assert!(requests.try_recv().is_err());
+        fs::remove_dir_all(path.parent().unwrap().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn error_status() {
+        let (url, _requests) = stub_server(400, "Puzzle inputs differ by user.");
+        let client = AocClient::new("expired").with_base_url(url);
+        let path = temp_input_file("status");
+
+        let err = client.ensure_input_at(1, &path).unwrap_err();
+        assert!(matches!(err, FetchError::Status { day: 1, status: 400 }));
+        assert!(err.to_string().contains("session token"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn unreachable_server() {
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = AocClient::new("secret").with_base_url(format!("http://127.0.0.1:{}", port));
+        assert!(matches!(client.fetch(1), Err(FetchError::Transport(_))));
+    }
+
+    #[test]
+    fn input_url() {
+        assert_eq!("https://adventofcode.com/2021/day/7/input", AocClient::new("x").input_url(7));
+    }
+}
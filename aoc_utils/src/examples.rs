@@ -0,0 +1,75 @@
+use crate::{normalize_input, Part, Solution};
+
+pub use aoc_macros::example_tests;
+
+/// Directory with the example inputs (`<name>.txt`) of a day, relative to the crate of the day
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// File in [`EXAMPLES_DIR`] with the expected answers of the examples
+///
+/// Same format as the workspace's [`crate::ANSWERS_FILE`], but with one section per example:
+///
+/// ```toml
+/// [example]
+/// star1 = "150"
+/// star2 = "900"
+/// ```
+pub const EXAMPLE_ANSWERS_FILE: &str = "answers.toml";
+
+/// Solves a part of an example and panics if the answer differs from the expected answer
+///
+/// Called by the tests generated with [`example_tests!`].
+pub fn check_example<S: Solution>(input: &str, part: Part, expected: &str) {
+    let input = S::parse(&normalize_input(input)).unwrap_or_else(|e| panic!("cannot parse example: {}", e));
+    let actual = match part {
+        Part::One => S::part1(&input).to_string(),
+        Part::Two => S::part2(&input).to_string(),
+    };
+
+    assert_eq!(expected, actual, "wrong answer for star {}", part.number());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, LineParser, NoAnswer, ParseError};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: i32 = 99;
+        const HAS_PART2: bool = false;
+        type Input = Vec<i32>;
+        type Answer1 = i32;
+        type Answer2 = NoAnswer;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            parse_lines(input, LineParser::number)
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            input.iter().sum()
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            NoAnswer
+        }
+    }
+
+    #[test]
+    fn correct_answer() {
+        check_example::<Sum>("1\n2\n3", Part::One, "6");
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong answer for star 1")]
+    fn wrong_answer() {
+        check_example::<Sum>("1\n2\n3", Part::One, "7");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot parse example: line 2, column 1")]
+    fn malformed_example() {
+        check_example::<Sum>("1\nx", Part::One, "1");
+    }
+}
@@ -0,0 +1,126 @@
+use std::ops::RangeInclusive;
+
+/// Seedable pseudo random number generator for generated inputs (SplitMix64)
+///
+/// Implemented here instead of using an external crate so that the numbers for a seed never change and
+/// a generated input can always be reproduced from its seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Creates a generator with the given seed
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    /// Next random 64-bit number
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random number below `n` (`n` must not be 0)
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        // Multiply-shift instead of modulo, the bias is negligible for the ranges used by the generators
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Random number in the given range (must not be empty)
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range");
+        // Computed in u64 so that the full range of i64 does not overflow (its length wraps to 0)
+        match (end as u64).wrapping_sub(start as u64).wrapping_add(1) {
+            0 => self.next_u64() as i64,
+            len => start.wrapping_add(self.below(len) as i64),
+        }
+    }
+
+    /// Returns `true` with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    /// Random element of a slice (must not be empty)
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Shuffles a slice in place (Fisher-Yates)
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Generator of random puzzle inputs for a day, used by `aoc gen`
+///
+/// Generated inputs follow the format and constraints of the puzzle, so that they can be used to stress
+/// the solutions with inputs that are larger than the real ones.
+#[derive(Debug, Clone, Copy)]
+pub struct InputGenerator {
+    /// Day of the puzzle
+    pub day: i32,
+    /// What the size parameter means (e.g. "number of depths")
+    pub size: &'static str,
+    /// Size of the real puzzle input
+    pub default_size: usize,
+    /// Generates an input of the given size
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+impl InputGenerator {
+    /// Generates an input with the given seed and size ([`InputGenerator::default_size`] if `None`)
+    pub fn generate(&self, seed: u64, size: Option<usize>) -> String {
+        (self.generate)(&mut Rng::new(seed), size.unwrap_or(self.default_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+        // The sequence of a seed must never change, otherwise generated inputs cannot be reproduced
+        assert_eq!(0xe220a8397b1dcdaf, Rng::new(0).next_u64());
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+            assert!(rng.below(10) < 10);
+        }
+
+        assert_eq!(5, rng.range(5..=5));
+        assert!((0..1000).any(|_| rng.range(-3..=3) == -3) && (0..1000).any(|_| rng.range(-3..=3) == 3));
+        assert!(!rng.chance(0.0) && rng.chance(1.0));
+        rng.range(i64::MIN..=i64::MAX);
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..100).collect();
+        rng.shuffle(&mut items);
+        assert_ne!((0..100).collect::<Vec<_>>(), items);
+        items.sort();
+        assert_eq!((0..100).collect::<Vec<_>>(), items);
+        assert!(items.contains(rng.choose(&items)));
+    }
+}
@@ -0,0 +1,474 @@
+use std::{
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    num::ParseIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{parse_line, LineParser, ParseError};
+
+/// Signed integer type used for the coordinates of points and cuboids
+pub trait Coordinate:
+    Copy + Default + Ord + Hash + Debug + Display + FromStr<Err = ParseIntError> + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self> + AddAssign + SubAssign + Into<i64>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    /// Absolute value
+    fn abs(self) -> Self;
+
+    /// -1, 0 or 1 depending on the sign
+    fn signum(self) -> Self;
+
+    /// Sum, `None` on overflow
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Difference, `None` on overflow
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64);
+
+/// Point (or vector) in 2D space
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Point (or vector) in 3D space
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Implements the vector arithmetic and distances for a point type with the given fields
+macro_rules! impl_point {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Coordinate> $point<T> {
+            /// Creates a point from its coordinates
+            pub fn new($($field: T),+) -> Self {
+                $point { $($field),+ }
+            }
+
+            /// Sum of the absolute coordinate differences
+            pub fn manhattan_distance(&self, other: &Self) -> T {
+                (*self - *other).manhattan_length()
+            }
+
+            /// Sum of the absolute coordinates (Manhattan distance to the origin)
+            pub fn manhattan_length(&self) -> T {
+                T::ZERO $(+ self.$field.abs())+
+            }
+
+            /// Square of the Euclidean length, exact unlike [`Self::length`]
+            pub fn length_squared(&self) -> i64 {
+                0 $(+ self.$field.into() * self.$field.into())+
+            }
+
+            /// Euclidean length
+            pub fn length(&self) -> f64 {
+                (self.length_squared() as f64).sqrt()
+            }
+
+            /// Euclidean distance
+            pub fn euclidean_distance(&self, other: &Self) -> f64 {
+                (*self - *other).length()
+            }
+
+            /// Sign of every coordinate, e.g. the step from one point towards another in a straight or diagonal line
+            pub fn signum(&self) -> Self {
+                $point { $($field: self.$field.signum()),+ }
+            }
+        }
+
+        impl<T: Coordinate> FromStr for $point<T> {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parse_line(s, Self::parse)
+            }
+        }
+
+        impl<T: Coordinate> Display for $point<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let coordinates = [$(self.$field.to_string()),+];
+                write!(f, "{}", coordinates.join(","))
+            }
+        }
+
+        impl<T: Coordinate> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $point { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Coordinate> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $point { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Coordinate> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Coordinate> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $point { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: Coordinate> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coordinate> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_point!(Point2 { x, y });
+impl_point!(Point3 { x, y, z });
+
+impl<T: Coordinate> Point2<T> {
+    /// Parses a point like `0,9`
+    pub fn parse(line: &mut LineParser) -> Result<Self, ParseError> {
+        let x = line.number()?;
+        line.literal(",")?;
+        Ok(Point2 { x, y: line.number()? })
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    /// Parses a point like `404,-588,-901`
+    pub fn parse(line: &mut LineParser) -> Result<Self, ParseError> {
+        let x = line.number()?;
+        line.literal(",")?;
+        let y = line.number()?;
+        line.literal(",")?;
+        Ok(Point3 { x, y, z: line.number()? })
+    }
+}
+
+/// Proper rotation in 3D space by multiples of 90 degrees, as matrix with the entries -1, 0 and 1
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rotation(pub [[i8; 3]; 3]);
+
+impl Rotation {
+    /// Rotation that does not change anything
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// Rotates a point around the origin
+    pub fn apply<T: Coordinate>(&self, p: Point3<T>) -> Point3<T> {
+        let row = |row: &[i8; 3]| {
+            [p.x, p.y, p.z].into_iter().zip(row).fold(T::ZERO, |sum, (c, m)| match m {
+                1 => sum + c,
+                -1 => sum - c,
+                _ => sum,
+            })
+        };
+
+        Point3::new(row(&self.0[0]), row(&self.0[1]), row(&self.0[2]))
+    }
+
+    /// Rotation that applies `other` first and then this rotation
+    pub fn after(&self, other: &Rotation) -> Rotation {
+        let mut result = [[0; 3]; 3];
+        for (i, row) in result.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.0[i][k] * other.0[k][j]).sum();
+            }
+        }
+
+        Rotation(result)
+    }
+
+    /// Rotation that undoes this one (the transposed matrix)
+    pub fn inverse(&self) -> Rotation {
+        let m = self.0;
+        Rotation([[m[0][0], m[1][0], m[2][0]], [m[0][1], m[1][1], m[2][1]], [m[0][2], m[1][2], m[2][2]]])
+    }
+}
+
+/// All 24 orientations of an object that can only be rotated by multiples of 90 degrees, starting with the identity
+///
+/// Grouped by viewing direction: 0/0/1, 0/0/-1, 1/0/0, -1/0/0, 0/1/0, 0/-1/0.
+pub const ROTATIONS: [Rotation; 24] = [
+    Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]),
+    Rotation([[0, 1, 0], [-1, 0, 0], [0, 0, 1]]),
+    Rotation([[-1, 0, 0], [0, -1, 0], [0, 0, 1]]),
+    Rotation([[0, -1, 0], [1, 0, 0], [0, 0, 1]]),
+    Rotation([[-1, 0, 0], [0, 1, 0], [0, 0, -1]]),
+    Rotation([[0, 1, 0], [1, 0, 0], [0, 0, -1]]),
+    Rotation([[1, 0, 0], [0, -1, 0], [0, 0, -1]]),
+    Rotation([[0, -1, 0], [-1, 0, 0], [0, 0, -1]]),
+    Rotation([[0, 0, -1], [0, 1, 0], [1, 0, 0]]),
+    Rotation([[0, 1, 0], [0, 0, 1], [1, 0, 0]]),
+    Rotation([[0, 0, 1], [0, -1, 0], [1, 0, 0]]),
+    Rotation([[0, -1, 0], [0, 0, -1], [1, 0, 0]]),
+    Rotation([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]),
+    Rotation([[0, 1, 0], [0, 0, -1], [-1, 0, 0]]),
+    Rotation([[0, 0, -1], [0, -1, 0], [-1, 0, 0]]),
+    Rotation([[0, -1, 0], [0, 0, 1], [-1, 0, 0]]),
+    Rotation([[0, 0, -1], [-1, 0, 0], [0, 1, 0]]),
+    Rotation([[-1, 0, 0], [0, 0, 1], [0, 1, 0]]),
+    Rotation([[0, 0, 1], [1, 0, 0], [0, 1, 0]]),
+    Rotation([[1, 0, 0], [0, 0, -1], [0, 1, 0]]),
+    Rotation([[0, 0, 1], [-1, 0, 0], [0, -1, 0]]),
+    Rotation([[-1, 0, 0], [0, 0, -1], [0, -1, 0]]),
+    Rotation([[0, 0, -1], [1, 0, 0], [0, -1, 0]]),
+    Rotation([[1, 0, 0], [0, 0, 1], [0, -1, 0]]),
+];
+
+/// Axis-aligned box of points between two corners (both inclusive)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Cuboid<T> {
+    /// Corner with the smallest coordinates
+    pub min: Point3<T>,
+    /// Corner with the largest coordinates
+    pub max: Point3<T>,
+}
+
+impl<T: Coordinate> Cuboid<T> {
+    /// Creates a cuboid from two opposite corners in any order
+    pub fn new(corner1: Point3<T>, corner2: Point3<T>) -> Self {
+        Cuboid {
+            min: Point3::new(corner1.x.min(corner2.x), corner1.y.min(corner2.y), corner1.z.min(corner2.z)),
+            max: Point3::new(corner1.x.max(corner2.x), corner1.y.max(corner2.y), corner1.z.max(corner2.z)),
+        }
+    }
+
+    /// Number of points in the cuboid
+    pub fn volume(&self) -> i64 {
+        let length = |min: T, max: T| max.into() - min.into() + 1;
+        length(self.min.x, self.max.x) * length(self.min.y, self.max.y) * length(self.min.z, self.max.z)
+    }
+
+    /// Whether the point lies inside the cuboid
+    pub fn contains(&self, p: &Point3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y) && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// Cuboid that is part of both cuboids, if any
+    pub fn intersection(&self, other: &Cuboid<T>) -> Option<Cuboid<T>> {
+        let min = Point3::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y), self.min.z.max(other.min.z));
+        let max = Point3::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y), self.max.z.min(other.max.z));
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid { min, max })
+    }
+
+    /// Whether the other cuboid lies completely inside this one
+    pub fn encloses(&self, other: &Cuboid<T>) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// Removes the other cuboid from this one, the remaining region is returned as up to 6 non-overlapping cuboids
+    pub fn cut(&self, other: &Cuboid<T>) -> Vec<Cuboid<T>> {
+        if other.encloses(self) {
+            return Vec::new();
+        }
+
+        if self.intersection(other).is_none() {
+            return vec![*self];
+        }
+
+        let (o1, o2) = (other.min, other.max);
+        let (min, max) = (T::MIN, T::MAX);
+        // A slab beyond the range of `T` (the other cuboid touches `T::MIN` or `T::MAX`) is empty
+        let (next, prev) = (|c: T| c.checked_add(T::ONE), |c: T| c.checked_sub(T::ONE));
+        let slabs = [
+            // Above and below the other cuboid
+            next(o2.y).map(|y| (Point3::new(min, y, min), Point3::new(max, max, max))),
+            prev(o1.y).map(|y| (Point3::new(min, min, min), Point3::new(max, y, max))),
+            // Left and right of it within its y range
+            prev(o1.x).map(|x| (Point3::new(min, o1.y, min), Point3::new(x, o2.y, max))),
+            next(o2.x).map(|x| (Point3::new(x, o1.y, min), Point3::new(max, o2.y, max))),
+            // In front of and behind it within its x and y range
+            prev(o1.z).map(|z| (Point3::new(o1.x, o1.y, min), Point3::new(o2.x, o2.y, z))),
+            next(o2.z).map(|z| (Point3::new(o1.x, o1.y, z), Point3::new(o2.x, o2.y, max))),
+        ];
+
+        slabs.into_iter().flatten().filter_map(|(c1, c2)| self.intersection(&Cuboid::new(c1, c2))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn cuboid(x1: i32, y1: i32, z1: i32, x2: i32, y2: i32, z2: i32) -> Cuboid<i32> {
+        Cuboid::new(Point3::new(x1, y1, z1), Point3::new(x2, y2, z2))
+    }
+
+    #[test]
+    fn points() {
+        let (p, q) = (Point3::new(1, -2, 3), Point3::new(-1, 0, 4));
+        assert_eq!(Point3::new(0, -2, 7), p + q);
+        assert_eq!(Point3::new(2, -2, -1), p - q);
+        assert_eq!(Point3::new(-2, 4, -6), -p * 2);
+        assert_eq!(5, p.manhattan_distance(&q));
+        assert_eq!(9, p.euclidean_distance(&q).powi(2).round() as i64);
+        assert_eq!(14, p.length_squared());
+
+        let mut r = Point2::<i16>::new(3, 0);
+        r += Point2::new(-5, 7);
+        assert_eq!(Point2::new(-1, 1), r.signum());
+        assert_eq!(5.0, Point2::new(3i8, 4).length());
+    }
+
+    #[test]
+    fn parse_points() {
+        assert_eq!(Ok(Point2::new(0i16, 9)), "0,9".parse());
+        assert_eq!(Ok(Point3::new(-618, -824, -621)), "-618,-824,-621".parse::<Point3<i32>>());
+        assert_eq!("-618,-824,-621", Point3::new(-618, -824, -621).to_string());
+        assert_eq!("line 1, column 5: expected number, found end of line", "1,2,".parse::<Point3<i32>>().unwrap_err().to_string());
+        assert!("1,2".parse::<Point3<i32>>().is_err());
+        assert!("1,99999".parse::<Point2<i16>>().is_err());
+    }
+
+    #[test]
+    fn rotations() {
+        assert_eq!(Rotation::IDENTITY, ROTATIONS[0]);
+        assert_eq!(24, ROTATIONS.iter().collect::<HashSet<_>>().len());
+
+        let p = Point3::new(1, 2, 3);
+        let rotated: HashSet<_> = ROTATIONS.iter().map(|r| r.apply(p)).collect();
+        assert_eq!(24, rotated.len());
+        assert!(rotated.iter().all(|r| r.length_squared() == p.length_squared()));
+
+        for r1 in ROTATIONS.iter() {
+            assert_eq!(p, r1.inverse().apply(r1.apply(p)));
+            for r2 in ROTATIONS.iter() {
+                assert!(ROTATIONS.contains(&r1.after(r2)));
+                assert_eq!(r1.apply(r2.apply(p)), r1.after(r2).apply(p));
+            }
+        }
+    }
+
+    #[test]
+    fn new_cuboid() {
+        assert_eq!(cuboid(-5, -5, -5, 5, 5, 5), cuboid(5, 5, 5, -5, -5, -5));
+        assert_eq!(cuboid(-5, -5, -5, 5, 5, 5), cuboid(5, -5, 5, -5, 5, -5));
+        assert!(cuboid(0, 0, 0, 2, 2, 2).contains(&Point3::new(2, 0, 1)));
+        assert!(!cuboid(0, 0, 0, 2, 2, 2).contains(&Point3::new(3, 0, 1)));
+    }
+
+    #[test]
+    fn intersection_partly() {
+        assert_eq!(Some(cuboid(5, 5, 5, 10, 10, 10)), cuboid(0, 0, 0, 10, 10, 10).intersection(&cuboid(5, 5, 5, 15, 15, 15)));
+        assert_eq!(Some(cuboid(-5, 0, -5, 0, 5, 5)), cuboid(-5, -5, -5, 5, 5, 5).intersection(&cuboid(-10, 0, -5, 0, 10, 5)));
+        assert_eq!(Some(cuboid(-5, -5, -5, 5, 5, 5)), cuboid(-5, -5, -5, 5, 5, 5).intersection(&cuboid(-5, -5, -5, 5, 5, 5)));
+        assert_eq!(Some(cuboid(1, 1, 1, 2, 2, 4)), cuboid(1, 0, 1, 4, 4, 4).intersection(&cuboid(0, 1, 0, 2, 2, 5)));
+    }
+
+    #[test]
+    fn intersection_fully() {
+        assert_eq!(Some(cuboid(-5, -5, -5, 5, 5, 5)), cuboid(-10, -10, -10, 10, 10, 10).intersection(&cuboid(-5, -5, -5, 5, 5, 5)));
+        assert_eq!(Some(cuboid(-5, -5, -5, 5, 5, 5)), cuboid(-5, -5, -5, 5, 5, 5).intersection(&cuboid(-10, -10, -10, 10, 10, 10)));
+    }
+
+    #[test]
+    fn no_intersection() {
+        assert_eq!(None, cuboid(0, 0, 0, 5, 5, 5).intersection(&cuboid(6, 6, 6, 15, 15, 15)));
+    }
+
+    #[test]
+    fn encloses() {
+        assert!(cuboid(0, 0, 0, 5, 5, 5).encloses(&cuboid(1, 1, 1, 4, 4, 4)));
+        assert!(!cuboid(1, 1, 1, 4, 4, 4).encloses(&cuboid(0, 0, 0, 5, 5, 5)));
+    }
+
+    #[test]
+    fn volume() {
+        assert_eq!(27, cuboid(0, 0, 0, 2, 2, 2).volume());
+        assert_eq!(64, cuboid(-1, -1, -1, 2, 2, 2).volume());
+        assert_eq!(64, cuboid(2, 2, 2, -1, -1, -1).volume());
+        assert_eq!(1 << 48, Cuboid::new(Point3::new(i16::MIN, i16::MIN, i16::MIN), Point3::new(i16::MAX, i16::MAX, i16::MAX)).volume());
+    }
+
+    #[test]
+    fn cut() {
+        let cube = cuboid(0, 0, 0, 5, 5, 5);
+        assert_eq!(vec![cube], cube.cut(&cuboid(6, 6, 6, 8, 8, 8)));
+
+        for (cube, other, parts) in [
+            (cuboid(0, 0, 0, 10, 10, 10), cuboid(1, 1, 1, 9, 9, 9), 6),
+            (cuboid(0, 0, 0, 10, 5, 5), cuboid(0, 0, 0, 5, 5, 5), 1),
+            (cuboid(0, 0, 0, 10, 5, 5), cuboid(5, 0, 0, 10, 5, 5), 1),
+            (cuboid(0, 0, 0, 10, 10, 10), cuboid(5, 5, 5, 10, 10, 10), 3),
+        ] {
+            let result = cube.cut(&other);
+            assert_eq!(parts, result.len());
+            assert_eq!(cube.volume() - other.volume(), result.iter().map(|c| c.volume()).sum::<i64>());
+            assert!(result.iter().all(|c| cube.encloses(c) && c.intersection(&other).is_none()));
+        }
+
+        assert!(cuboid(1, 1, 1, 1, 1, 1).cut(&cuboid(1, 1, 1, 1, 1, 1)).is_empty());
+    }
+
+    #[test]
+    fn cut_at_boundaries() {
+        let (min, max) = (i8::MIN, i8::MAX);
+        let all = Cuboid::new(Point3::new(min, min, min), Point3::new(max, max, max));
+        for other in [
+            Cuboid::new(Point3::new(min, min, min), Point3::new(0, 0, 0)),
+            Cuboid::new(Point3::new(0, 0, 0), Point3::new(max, max, max)),
+            Cuboid::new(Point3::new(min, -5, 0), Point3::new(max, 5, max)),
+            all,
+        ] {
+            let result = all.cut(&other);
+            assert_eq!(all.volume() - other.volume(), result.iter().map(|c| c.volume()).sum::<i64>());
+            assert!(result.iter().all(|c| c.intersection(&other).is_none()));
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use std::{
+    error::Error,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// Position in a [`Grid`] as `(x, y)`, i.e. `(column, row)`
+pub type Pos = (usize, usize);
+
+/// Neighborhood used when iterating over the neighbors of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Up, down, left and right (von Neumann neighborhood)
+    Four,
+    /// Including diagonals (Moore neighborhood)
+    Eight,
+}
+
+impl Neighborhood {
+    const FOUR: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+    const EIGHT: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+    /// Offsets `(dx, dy)` of the neighbors in row-major order
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Four => &Self::FOUR,
+            Neighborhood::Eight => &Self::EIGHT,
+        }
+    }
+}
+
+/// Errors that can occur while parsing a character map into a [`Grid`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// Input contains no rows
+    Empty,
+    /// Row (1-based) has a different length than the first row
+    UnevenRow { row: usize, expected: usize, actual: usize },
+    /// Cell mapping rejected the character at the given row and column (both 1-based)
+    InvalidCell { row: usize, col: usize, ch: char },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::UnevenRow { row, expected, actual } => write!(f, "row {} has {} cells, expected {}", row, actual, expected),
+            GridError::InvalidCell { row, col, ch } => write!(f, "invalid cell '{}' at row {}, column {}", ch, row, col),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// Rectangular 2D grid stored in row-major order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order
+    ///
+    /// Panics if the number of cells does not match `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "number of cells does not match grid size");
+        Grid { width, height, cells }
+    }
+
+    /// Creates a grid by calling `f` with the position of each cell
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(&mut f).collect();
+        Grid { width, height, cells }
+    }
+
+    /// Creates a grid with all cells set to `value`
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses a character map, one row per line
+    ///
+    /// `f` maps each character to a cell, `None` rejects the character. Trailing empty lines and
+    /// `\r` line endings are ignored.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let lines: Vec<&str> = input.lines().map(|l| l.strip_suffix('\r').unwrap_or(l)).collect();
+        let lines = &lines[..lines.iter().rposition(|l| !l.is_empty()).map_or(0, |ix| ix + 1)];
+        let width = lines.first().ok_or(GridError::Empty)?.chars().count();
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let len = line.chars().count();
+            if len != width {
+                return Err(GridError::UnevenRow {
+                    row: row + 1,
+                    expected: width,
+                    actual: len,
+                });
+            }
+
+            for (col, ch) in line.chars().enumerate() {
+                cells.push(f(ch).ok_or(GridError::InvalidCell { row: row + 1, col: col + 1, ch })?);
+            }
+        }
+
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    /// Number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the grid has no cells
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn index_of(&self, (x, y): Pos) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Cell at the given position, `None` if out of bounds
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|ix| &self.cells[ix])
+    }
+
+    /// Mutable cell at the given position, `None` if out of bounds
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(move |ix| &mut self.cells[ix])
+    }
+
+    /// Cell at a signed position, `None` if out of bounds (e.g. negative)
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        self.get((usize::try_from(x).ok()?, usize::try_from(y).ok()?))
+    }
+
+    /// Position moved by `(dx, dy)`, `None` if the result is out of bounds
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.index_of(pos).map(|_| pos)
+    }
+
+    /// Position moved by `(dx, dy)`, wrapping around the edges (torus)
+    pub fn wrapping_offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Pos {
+        fn wrap(v: usize, d: isize, len: usize) -> usize {
+            (v as isize + d).rem_euclid(len as isize) as usize
+        }
+
+        (wrap(x, dx, self.width), wrap(y, dy, self.height))
+    }
+
+    /// Positions of the neighbors of a cell that are inside the grid
+    pub fn neighbors(&self, pos: Pos, neighborhood: Neighborhood) -> impl Iterator<Item = Pos> + '_ {
+        neighborhood.offsets().iter().filter_map(move |d| self.offset(pos, *d))
+    }
+
+    /// Positions of the neighbors of a cell, wrapping around the edges (torus)
+    pub fn wrapping_neighbors(&self, pos: Pos, neighborhood: Neighborhood) -> impl Iterator<Item = Pos> + '_ {
+        neighborhood.offsets().iter().map(move |d| self.wrapping_offset(pos, *d))
+    }
+
+    /// Cells of a row
+    ///
+    /// Panics if the row is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {} out of bounds", y);
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Mutable cells of a row
+    ///
+    /// Panics if the row is out of bounds.
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.height, "row {} out of bounds", y);
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over all rows
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Cells of a column
+    ///
+    /// Panics if the column is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates over all columns
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Iterates over all cells in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Iterates over all cells with their positions in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(ix, c)| ((ix % width, ix / width), c))
+    }
+
+    /// Position of the first cell matching the predicate
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells.iter().position(predicate).map(|ix| (ix % self.width, ix / self.width))
+    }
+
+    /// Creates a new grid by mapping every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Creates a new grid with rows and columns swapped
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        match self.index_of(pos) {
+            Some(ix) => &self.cells[ix],
+            None => panic!("position {:?} out of bounds of {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        match self.index_of(pos) {
+            Some(ix) => &mut self.cells[ix],
+            None => panic!("position {:?} out of bounds of {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+/// Renders the grid with one line per row (without trailing newline)
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = digits("123\r\n456\n\n");
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(6, grid[(2, 1)]);
+        assert_eq!(Some(&4), grid.get((0, 1)));
+        assert_eq!(None, grid.get((3, 0)));
+
+        for input in crate::line_ending_variants("123\n456") {
+            assert_eq!(grid, digits(&input));
+        }
+        assert_eq!(grid, digits("123\r\n456\r"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Err(GridError::Empty), Grid::parse("\n", |c| c.to_digit(10)));
+        assert_eq!(Err(GridError::UnevenRow { row: 2, expected: 3, actual: 2 }), Grid::parse("123\n45", |c| c.to_digit(10)));
+        assert_eq!(Err(GridError::InvalidCell { row: 2, col: 2, ch: 'x' }), Grid::parse("123\n4x6", |c| c.to_digit(10)));
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = digits("123\n456\n789");
+        let values = |n: Vec<Pos>| n.into_iter().map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(vec![2, 4], values(grid.neighbors((0, 0), Neighborhood::Four).collect()));
+        assert_eq!(vec![2, 4, 5], values(grid.neighbors((0, 0), Neighborhood::Eight).collect()));
+        assert_eq!(vec![1, 2, 3, 4, 6, 7, 8, 9], values(grid.neighbors((1, 1), Neighborhood::Eight).collect()));
+        assert_eq!(vec![7, 3, 2, 4], values(grid.wrapping_neighbors((0, 0), Neighborhood::Four).collect()));
+    }
+
+    #[test]
+    fn offsets() {
+        let grid = digits("12\n34");
+        assert_eq!(Some((1, 0)), grid.offset((0, 0), (1, 0)));
+        assert_eq!(None, grid.offset((0, 0), (-1, 0)));
+        assert_eq!((0, 1), grid.wrapping_offset((1, 1), (1, 0)));
+        assert_eq!((1, 1), grid.wrapping_offset((0, 0), (-1, -1)));
+        assert_eq!(None, grid.get_signed(-1, 0));
+        assert_eq!(Some(&3), grid.get_signed(0, 1));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456");
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![3, 6], grid.column(2).copied().collect::<Vec<_>>());
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(vec![5, 7, 9], grid.columns().map(|c| c.sum()).collect::<Vec<u32>>());
+        assert_eq!(Some((1, 1)), grid.position(|v| *v == 5));
+    }
+
+    #[test]
+    fn transpose() {
+        let grid = digits("123\n456").transpose();
+        assert_eq!("14\n25\n36", grid.to_string());
+    }
+
+    #[test]
+    fn from_fn_and_map() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(vec![((0, 1), &10)], grid.positions().filter(|(_, v)| **v == 10).collect::<Vec<_>>());
+        assert_eq!("#..\n#..", grid.map(|v| if v % 10 == 0 { '#' } else { '.' }).to_string());
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    fmt::{self, Write as _},
+    path::PathBuf,
+};
+
+use crate::{get_input_file_name, Grid, InputSource, ParseError, Rgb};
+
+/// Black and white image as binary PBM (`P4`), `true` pixels are black
+pub fn encode_pbm(image: &Grid<bool>) -> Vec<u8> {
+    let mut data = format!("P4\n{} {}\n", image.width(), image.height()).into_bytes();
+    for row in image.rows() {
+        // Rows are padded to whole bytes, the first pixel is the most significant bit
+        for chunk in row.chunks(8) {
+            data.push(chunk.iter().enumerate().fold(0, |byte, (ix, black)| byte | (u8::from(*black) << (7 - ix))));
+        }
+    }
+
+    data
+}
+
+/// Grayscale image as binary PGM (`P5`), 0 is black and 255 is white
+pub fn encode_pgm(image: &Grid<u8>) -> Vec<u8> {
+    let mut data = format!("P5\n{} {}\n255\n", image.width(), image.height()).into_bytes();
+    data.extend(image.iter());
+    data
+}
+
+/// Color image as binary PPM (`P6`)
+pub fn encode_ppm(image: &Grid<Rgb>) -> Vec<u8> {
+    let mut data = format!("P6\n{} {}\n255\n", image.width(), image.height()).into_bytes();
+    image.iter().for_each(|Rgb(r, g, b)| data.extend([r, g, b]));
+    data
+}
+
+/// How an SVG element is drawn, elements without stroke and fill are invisible
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub stroke: Option<Rgb>,
+    pub stroke_width: f64,
+    pub fill: Option<Rgb>,
+    /// 0 is transparent, 1 is opaque
+    pub opacity: f64,
+}
+
+impl Style {
+    /// Outline with the given color and width
+    pub fn stroke(color: Rgb, width: f64) -> Self {
+        Style {
+            stroke: Some(color),
+            stroke_width: width,
+            fill: None,
+            opacity: 1.0,
+        }
+    }
+
+    /// Area filled with the given color
+    pub fn fill(color: Rgb) -> Self {
+        Style {
+            stroke: None,
+            stroke_width: 0.0,
+            fill: Some(color),
+            opacity: 1.0,
+        }
+    }
+
+    /// Same style with the given opacity
+    pub fn with_opacity(self, opacity: f64) -> Self {
+        Style { opacity, ..self }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let color = |c: Option<Rgb>| c.map_or("none".to_string(), |Rgb(r, g, b)| format!("#{:02x}{:02x}{:02x}", r, g, b));
+        write!(f, "fill=\"{}\" stroke=\"{}\"", color(self.fill), color(self.stroke))?;
+        if self.stroke.is_some() {
+            write!(f, " stroke-width=\"{}\"", number(self.stroke_width))?;
+        }
+
+        if self.opacity < 1.0 {
+            write!(f, " opacity=\"{}\"", number(self.opacity))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Number with at most two decimals, to keep drawings with many points small
+fn number(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// SVG drawing with lines, rectangles and polylines, rendered with `to_string`
+///
+/// Coordinates are in pixels with the origin at the top left corner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    width: f64,
+    height: f64,
+    elements: Vec<String>,
+}
+
+impl Svg {
+    /// Creates an empty drawing of the given size
+    pub fn new(width: f64, height: f64) -> Self {
+        Svg { width, height, elements: Vec::new() }
+    }
+
+    /// Adds a line between two points
+    pub fn line(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), style: &Style) -> &mut Self {
+        self.elements
+            .push(format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>", number(x1), number(y1), number(x2), number(y2), style));
+        self
+    }
+
+    /// Adds a rectangle with the given top left corner and size
+    pub fn rect(&mut self, (x, y): (f64, f64), (width, height): (f64, f64), style: &Style) -> &mut Self {
+        self.elements.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+            number(x),
+            number(y),
+            number(width),
+            number(height),
+            style
+        ));
+        self
+    }
+
+    /// Adds connected line segments through the given points
+    pub fn polyline(&mut self, points: &[(f64, f64)], style: &Style) -> &mut Self {
+        let mut element = "<polyline points=\"".to_string();
+        for (ix, (x, y)) in points.iter().enumerate() {
+            write!(element, "{}{},{}", if ix == 0 { "" } else { " " }, number(*x), number(*y)).unwrap();
+        }
+
+        write!(element, "\" {}/>", style).unwrap();
+        self.elements.push(element);
+        self
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">", number(self.width), number(self.height))?;
+        self.elements.iter().try_for_each(|e| writeln!(f, "  {}", e))?;
+        writeln!(f, "</svg>")
+    }
+}
+
+/// Image of a puzzle state of a day, used by `aoc export`
+#[derive(Debug, Clone, Copy)]
+pub struct ImageExport {
+    pub day: i32,
+    /// File extension of the format (`pbm`, `pgm`, `ppm` or `svg`)
+    pub extension: &'static str,
+    /// What the image shows (e.g. "image after 50 enhancements")
+    pub description: &'static str,
+    /// Creates the file content for a puzzle input
+    pub render: fn(&str) -> Result<Vec<u8>, ParseError>,
+}
+
+impl ImageExport {
+    /// Path of the image next to the input file, e.g. `day20/input.pbm` for `day20/input.txt`
+    pub fn default_path(&self, input: &InputSource) -> PathBuf {
+        match input {
+            InputSource::File(path) => path.with_extension(self.extension),
+            InputSource::Stdin => get_input_file_name(self.day).with_extension(self.extension),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn netpbm() {
+        let bits = Grid::from_fn(10, 2, |(x, y)| x == y || x == 9);
+        assert_eq!(b"P4\n10 2\n\x80\x40\x40\x40".as_slice(), encode_pbm(&bits));
+
+        let gray = Grid::from_fn(3, 1, |(x, _)| x as u8 * 100);
+        assert_eq!(b"P5\n3 1\n255\n\x00\x64\xc8".as_slice(), encode_pgm(&gray));
+
+        let colors = Grid::from_fn(2, 1, |(x, _)| if x == 0 { Rgb(1, 2, 3) } else { Rgb(255, 0, 128) });
+        assert_eq!(b"P6\n2 1\n255\n\x01\x02\x03\xff\x00\x80".as_slice(), encode_ppm(&colors));
+    }
+
+    #[test]
+    fn svg() {
+        let mut svg = Svg::new(100.0, 50.5);
+        svg.rect((1.0, 2.0), (10.0, 5.0), &Style::fill(Rgb(255, 0, 0)))
+            .line((0.0, 0.0), (3.5, 4.0), &Style::stroke(Rgb(0, 0, 0), 0.5))
+            .polyline(&[(0.0, 0.0), (1.0, 2.0), (3.0, 1.0 / 3.0)], &Style::stroke(Rgb(0, 128, 255), 1.0).with_opacity(0.25));
+
+        assert_eq!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"50.5\">\n\
+             \x20 <rect x=\"1\" y=\"2\" width=\"10\" height=\"5\" fill=\"#ff0000\" stroke=\"none\"/>\n\
+             \x20 <line x1=\"0\" y1=\"0\" x2=\"3.5\" y2=\"4\" fill=\"none\" stroke=\"#000000\" stroke-width=\"0.5\"/>\n\
+             \x20 <polyline points=\"0,0 1,2 3,0.33\" fill=\"none\" stroke=\"#0080ff\" stroke-width=\"1\" opacity=\"0.25\"/>\n\
+             </svg>\n",
+            svg.to_string()
+        );
+    }
+
+    #[test]
+    fn default_path() {
+        let export = ImageExport {
+            day: 20,
+            extension: "pbm",
+            description: "test",
+            render: |_| Ok(Vec::new()),
+        };
+
+        assert_eq!(PathBuf::from("/tmp/x.pbm"), export.default_path(&InputSource::File(PathBuf::from("/tmp/x.txt"))));
+        assert_eq!(get_input_file_name(20).with_extension("pbm"), export.default_path(&InputSource::Stdin));
+    }
+}
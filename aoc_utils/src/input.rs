@@ -0,0 +1,422 @@
+use std::{
+    borrow::Cow,
+    env,
+    error::Error,
+    ffi::{OsStr, OsString},
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::ParseError;
+
+/// Environment variable pointing to a directory with `dayNN/input.txt` files
+///
+/// Can be used to run the solutions against alternate datasets.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// Source from which the input of a day is read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// Input file at the given path
+    File(PathBuf),
+    /// Standard input of the process
+    Stdin,
+}
+
+impl InputSource {
+    /// Resolves the input source for a given day based on the command line of the current process
+    pub fn for_day(day: i32) -> Self {
+        Self::from_args(day, env::args_os().skip(1))
+    }
+
+    /// Resolves the input source for a given day based on the given command line arguments
+    ///
+    /// `--input <path>` (or `--input=<path>`) selects an explicit input file, `-` as path
+    /// (or as standalone argument) reads from stdin. Without these arguments, the
+    /// input file is located with [`get_input_file_name`].
+    pub fn from_args<I, S>(day: i32, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            if arg == "-" {
+                return InputSource::Stdin;
+            }
+
+            let path = if arg == "--input" {
+                match args.next() {
+                    Some(path) => path.as_ref().to_os_string(),
+                    None => break,
+                }
+            } else if let Some(path) = arg.to_str().and_then(|a| a.strip_prefix("--input=")) {
+                OsString::from(path)
+            } else {
+                continue;
+            };
+
+            return if path == "-" { InputSource::Stdin } else { InputSource::File(PathBuf::from(path)) };
+        }
+
+        InputSource::File(get_input_file_name(day))
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "input file {}", path.display()),
+            InputSource::Stdin => write!(f, "input from stdin"),
+        }
+    }
+}
+
+/// Errors that can occur while loading the input of a day
+#[derive(Debug)]
+pub enum InputError {
+    /// Input file does not exist at the resolved path
+    NotFound(PathBuf),
+    /// Input file exists, but we are not allowed to read it
+    PermissionDenied(PathBuf),
+    /// Input is not valid UTF-8; `offset` is the index of the first invalid byte
+    InvalidUtf8 { input: InputSource, offset: usize },
+    /// Input contains nothing but whitespace
+    Empty(InputSource),
+    /// Any other I/O error
+    Io(InputSource, io::Error),
+    /// Input was read, but the solution cannot parse it
+    Parse(InputSource, ParseError),
+}
+
+impl InputError {
+    pub(crate) fn from_io(input: InputSource, err: io::Error) -> Self {
+        match (input, err.kind()) {
+            (InputSource::File(path), io::ErrorKind::NotFound) => InputError::NotFound(path),
+            (InputSource::File(path), io::ErrorKind::PermissionDenied) => InputError::PermissionDenied(path),
+            (input, _) => InputError::Io(input, err),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound(path) => write!(f, "input file {} not found", path.display()),
+            InputError::PermissionDenied(path) => write!(f, "permission denied while reading input file {}", path.display()),
+            InputError::InvalidUtf8 { input, offset } => write!(f, "{} is not valid UTF-8 (invalid byte at offset {})", input, offset),
+            InputError::Empty(input) => write!(f, "{} is empty", input),
+            InputError::Io(input, err) => write!(f, "cannot read {}: {}", input, err),
+            InputError::Parse(input, err) => write!(f, "invalid {}: {}", input, err),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io(_, err) => Some(err),
+            InputError::Parse(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Constructs the path and file name for an input file for given day
+///
+/// Lookup order:
+/// * `$AOC_INPUT_DIR/dayNN/input.txt` if [`INPUT_DIR_ENV`] is set
+/// * `dayNN/input.txt` relative to the current directory if it exists
+/// * `dayNN/input.txt` relative to the workspace root
+pub fn get_input_file_name(day: i32) -> PathBuf {
+    resolve_input_file_name(day, env::var_os(INPUT_DIR_ENV))
+}
+
+fn resolve_input_file_name(day: i32, input_dir: Option<OsString>) -> PathBuf {
+    let relative = Path::new(format!("day{:0>2}", day).as_str()).join("input.txt");
+    if let Some(input_dir) = input_dir.filter(|d| !d.is_empty()) {
+        return Path::new(&input_dir).join(relative);
+    }
+
+    if relative.exists() {
+        return relative;
+    }
+
+    match find_workspace_root() {
+        Some(root) => root.join(relative),
+        None => relative,
+    }
+}
+
+/// Locates the root directory of the cargo workspace
+///
+/// Starts at `CARGO_MANIFEST_DIR` (set by `cargo run`/`cargo test`, falls back to the
+/// directory this crate was built from) and walks up until a `Cargo.toml` with a
+/// `[workspace]` section is found.
+pub fn find_workspace_root() -> Option<PathBuf> {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from).unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+    manifest_dir
+        .ancestors()
+        .find(|dir| fs::read_to_string(dir.join("Cargo.toml")).map(|m| m.lines().any(|l| l.trim() == "[workspace]")).unwrap_or(false))
+        .map(Path::to_path_buf)
+}
+
+/// Reads the input file for a given day into a string
+///
+/// Panics if the file cannot be read. Use [`try_read_input_file`] to handle errors.
+pub fn read_input_file(day: i32) -> String {
+    try_read_input_file(day).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Reads the input file for a given day into a byte vector
+///
+/// Panics if the file cannot be read. Use [`try_read_input_file_into_bytes`] to handle errors.
+pub fn read_input_file_into_bytes(day: i32) -> Vec<u8> {
+    try_read_input_file_into_bytes(day).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Reads the input file for a given day into a string
+///
+/// The file is located with [`get_input_file_name`], the command line is not consulted. Use [`try_read_input`]
+/// with [`InputSource::for_day`] for an input that can be changed on the command line.
+pub fn try_read_input_file(day: i32) -> Result<String, InputError> {
+    try_read_input(InputSource::File(get_input_file_name(day)))
+}
+
+/// Reads the input file for a given day into a byte vector
+///
+/// The file is located with [`get_input_file_name`], like in [`try_read_input_file`].
+pub fn try_read_input_file_into_bytes(day: i32) -> Result<Vec<u8>, InputError> {
+    try_read_input_into_bytes(InputSource::File(get_input_file_name(day)))
+}
+
+/// Reads the given input source into a string
+pub fn try_read_input(input: InputSource) -> Result<String, InputError> {
+    let content = try_read_input_into_bytes(input.clone())?;
+    String::from_utf8(content).map_err(|e| InputError::InvalidUtf8 {
+        input,
+        offset: e.utf8_error().valid_up_to(),
+    })
+}
+
+/// Reads the given input source into a byte vector
+pub fn try_read_input_into_bytes(input: InputSource) -> Result<Vec<u8>, InputError> {
+    let read = |input: &InputSource| -> io::Result<Vec<u8>> {
+        match input {
+            InputSource::File(path) => {
+                let mut f = File::open(path)?;
+                let md = f.metadata()?;
+                let mut result = Vec::<u8>::with_capacity(md.len() as usize);
+                f.read_to_end(&mut result)?;
+                Ok(result)
+            }
+            InputSource::Stdin => {
+                let mut result = Vec::<u8>::new();
+                io::stdin().lock().read_to_end(&mut result)?;
+                Ok(result)
+            }
+        }
+    };
+
+    match read(&input) {
+        Ok(content) if content.iter().all(u8::is_ascii_whitespace) => Err(InputError::Empty(input)),
+        Ok(content) => Ok(content),
+        Err(e) => Err(InputError::from_io(input, e)),
+    }
+}
+
+/// Converts `\r\n` line endings to `\n` and removes trailing blank lines and the final line break
+///
+/// Applied to every input before it is passed to [`crate::Solution::parse`], so that parsers see the same
+/// text regardless of how the input file was saved.
+pub fn normalize_input(input: &str) -> Cow<'_, str> {
+    let input = input.trim_end();
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Variants of the input with `\n` and `\r\n` line endings, each with and without a final line break
+///
+/// Used in tests to make sure that a parser does not depend on how the input file was saved.
+pub fn line_ending_variants(input: &str) -> [String; 4] {
+    let lf = normalize_input(input).into_owned();
+    let crlf = lf.replace('\n', "\r\n");
+    [format!("{}\n", lf), format!("{}\r\n", crlf), lf, crlf]
+}
+
+/// Opens the given input source for buffered reading without loading it into memory
+pub fn open_input(input: InputSource) -> Result<Box<dyn BufRead>, InputError> {
+    match &input {
+        InputSource::File(path) => match File::open(path) {
+            Ok(f) => Ok(Box::new(BufReader::new(f))),
+            Err(e) => Err(InputError::from_io(input, e)),
+        },
+        InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+    }
+}
+
+/// Opens the input file for a given day for buffered reading
+///
+/// The file is located with [`get_input_file_name`], like in [`try_read_input_file`]. Use [`read_lines`] to
+/// iterate over the lines.
+pub fn input_reader(day: i32) -> Result<impl BufRead, InputError> {
+    open_input(InputSource::File(get_input_file_name(day)))
+}
+
+/// Iterates over the non-empty lines of a reader with their line numbers (1-based)
+///
+/// Like [`crate::input_lines`] for inputs that are not in memory: `\n` and `\r\n` are not part of the line.
+pub fn read_lines<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<(usize, String)>> {
+    reader.lines().enumerate().filter_map(|(ix, line)| match line {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(mut line) => {
+            if line.ends_with('\r') {
+                line.pop();
+            }
+
+            Some(Ok((ix + 1, line)))
+        }
+        Err(e) => Some(Err(e)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_digit() {
+        let res = get_input_file_name(1);
+        assert!(res.ends_with(Path::new("day01").join("input.txt")), "path = '{:?}'", res);
+    }
+
+    #[test]
+    fn double_digit() {
+        let res = get_input_file_name(10);
+        assert!(res.ends_with(Path::new("day10").join("input.txt")), "path = '{:?}'", res);
+    }
+
+    #[test]
+    fn relative_to_current_dir() {
+        let res = resolve_input_file_name(99, None);
+        assert_eq!(Path::new("day99").join("input.txt"), res);
+    }
+
+    #[test]
+    fn relative_to_workspace_root() {
+        let res = resolve_input_file_name(1, None);
+        assert!(res.is_absolute(), "path = '{:?}'", res);
+        assert!(res.exists(), "path = '{:?}'", res);
+    }
+
+    #[test]
+    fn relative_to_input_dir() {
+        let res = resolve_input_file_name(5, Some(OsString::from("/data/aoc")));
+        assert_eq!(Path::new("/data/aoc").join("day05").join("input.txt"), res);
+    }
+
+    #[test]
+    fn workspace_root() {
+        let root = find_workspace_root().unwrap();
+        assert!(root.join("aoc_utils").is_dir(), "root = '{:?}'", root);
+    }
+
+    #[test]
+    fn args_without_input() {
+        assert_eq!(InputSource::File(get_input_file_name(1)), InputSource::from_args(1, ["--part", "1"]));
+    }
+
+    #[test]
+    fn args_with_input() {
+        assert_eq!(InputSource::File(PathBuf::from("other.txt")), InputSource::from_args(1, ["--input", "other.txt"]));
+        assert_eq!(InputSource::File(PathBuf::from("other.txt")), InputSource::from_args(1, ["--input=other.txt"]));
+    }
+
+    #[test]
+    fn args_with_stdin() {
+        assert_eq!(InputSource::Stdin, InputSource::from_args(1, ["-"]));
+        assert_eq!(InputSource::Stdin, InputSource::from_args(1, ["--input", "-"]));
+        assert_eq!(InputSource::Stdin, InputSource::from_args(1, ["--input=-"]));
+    }
+
+    #[test]
+    fn read_day99_input() {
+        let res = read_input_file(99);
+        assert_eq!(res, "Test", "file content = '{:?}'", res);
+    }
+
+    #[test]
+    fn read_day99_input_bytes() {
+        let res = read_input_file_into_bytes(99);
+        assert_eq!(4, res.len());
+        assert_eq!(b'T', res[0]);
+    }
+
+    #[test]
+    fn read_explicit_input() {
+        let res = try_read_input(InputSource::File(Path::new("day99").join("input.txt"))).unwrap();
+        assert_eq!("Test", res);
+    }
+
+    #[test]
+    fn try_read_missing_input() {
+        // Day-keyed loaders only use the default location, whatever the command line of the test binary is
+        let res = try_read_input_file(42);
+        assert!(
+            matches!(res, Err(InputError::NotFound(ref p)) if p.ends_with(Path::new("day42").join("input.txt")) && *p == get_input_file_name(42)),
+            "result = '{:?}'",
+            res
+        );
+    }
+
+    #[test]
+    fn try_read_empty_input() {
+        let res = try_read_input_file(98);
+        assert!(matches!(res, Err(InputError::Empty(_))), "result = '{:?}'", res);
+    }
+
+    #[test]
+    fn try_read_invalid_utf8_input() {
+        let res = try_read_input_file(97);
+        assert!(matches!(res, Err(InputError::InvalidUtf8 { offset: 2, .. })), "result = '{:?}'", res);
+
+        // Reading bytes does not care about UTF-8
+        assert_eq!(5, try_read_input_file_into_bytes(97).unwrap().len());
+    }
+
+    #[test]
+    fn normalize() {
+        assert!(matches!(normalize_input("1\n\n2"), Cow::Borrowed("1\n\n2")));
+        assert_eq!("1\n\n2", normalize_input("1\r\n\r\n2\r\n\r\n"));
+        assert_eq!("1\n2", normalize_input("1\n2\n \n"));
+        for variant in line_ending_variants("1\n\n2\n") {
+            assert_eq!("1\n\n2", normalize_input(&variant));
+        }
+    }
+
+    #[test]
+    fn read_lines_of_reader() {
+        let lines: Vec<_> = read_lines("1\r\n2\n\n  \n3\r".as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(vec![(1, "1".to_string()), (2, "2".to_string()), (5, "3".to_string())], lines);
+        assert!(read_lines(&b"1\n\xff\n"[..]).any(|l| l.is_err()));
+    }
+
+    #[test]
+    fn open_input_reader() {
+        let lines: Vec<_> = read_lines(input_reader(99).unwrap()).map(Result::unwrap).collect();
+        assert_eq!(vec![(1, "Test".to_string())], lines);
+        assert!(matches!(open_input(InputSource::File(get_input_file_name(42))), Err(InputError::NotFound(_))));
+    }
+
+    #[test]
+    fn error_message_contains_path() {
+        let res = try_read_input_file(42).unwrap_err().to_string();
+        assert!(res.contains(&get_input_file_name(42).display().to_string()), "message = '{}'", res);
+    }
+}
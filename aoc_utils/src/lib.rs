@@ -1,58 +1,63 @@
-use std::{fs::{self, File}, path::{Path, PathBuf}, io::Read};
+use std::{error::Error, process};
 
 use termion::{color, style};
 
-/// Constructs the path and file name for an input file for given day
-pub fn get_input_file_name(day: i32) -> PathBuf {
-    Path::new(format!("day{:0>2}", day).as_str()).join("input.txt")
-}
-
-/// Reads the input file for a given day into a string
-pub fn read_input_file(day: i32) -> String {
-    fs::read_to_string(get_input_file_name(day)).unwrap()
-}
-
-/// Reads the input file for a given day into a string
-pub fn read_input_file_into_bytes(day: i32) -> Vec<u8> {
-    let input_file_name = get_input_file_name(day);
-    let mut f = File::open(input_file_name).unwrap();
-    let md = f.metadata().unwrap();
-    let mut result = Vec::<u8>::with_capacity(md.len() as usize);
-    f.read_to_end(&mut result).unwrap();
-    result
-}
-
-/// Prints colored day header
+mod bench;
+mod budget;
+mod client;
+mod examples;
+mod generator;
+mod geometry;
+mod grid;
+mod image;
+mod input;
+mod memory;
+mod output;
+mod parallel;
+mod parse;
+mod runner;
+mod solution;
+#[cfg(test)]
+mod stub_server;
+mod submit;
+mod trace;
+mod verify;
+mod visualize;
+mod watch;
+
+pub use bench::*;
+pub use budget::*;
+pub use client::*;
+pub use examples::*;
+pub use generator::*;
+pub use geometry::*;
+pub use grid::*;
+pub use image::*;
+pub use input::*;
+pub use memory::*;
+pub use output::*;
+pub use parallel::*;
+pub use parse::*;
+pub use runner::*;
+pub use solution::*;
+pub use submit::*;
+pub use trace::*;
+pub use verify::*;
+pub use visualize::*;
+pub use watch::*;
+
+/// Prints day header (colored if [`color_enabled`])
 pub fn print_day_header(day: i32) {
-    println!("{}{}Day {}:{}", style::Bold, color::Fg(color::Yellow), day, style::Reset);
+    println!("{}", output::day_header(day));
 }
 
-/// Tests for star 1
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn single_digit() {
-        let res = get_input_file_name(1);
-        assert!(res.starts_with("day01"), "path = '{:?}'", res);
-    }
-
-    #[test]
-    fn double_digit() {
-        let res = get_input_file_name(10);
-        assert!(res.starts_with("day10"), "path = '{:?}'", res);
+/// Prints an error to stderr and terminates the process with a non-zero exit code
+pub fn exit_with_error(err: &dyn Error) -> ! {
+    if stderr_color_enabled() {
+        eprintln!("{}{}Error:{} {}", style::Bold, color::Fg(color::Red), style::Reset, err);
+    } else {
+        eprintln!("Error: {}", err);
     }
 
-    #[test]
-    fn read_day99_input() {
-        let res = read_input_file(99);
-        assert_eq!(res, "Test", "file content = '{:?}'", res);
-    }
-
-    #[test]
-    fn read_day99_input_bytes() {
-        let res = read_input_file_into_bytes(99);
-        assert_eq!(4, res.len());
-        assert_eq!(b'T', res[0]);
-    }}
\ No newline at end of file
+    process::exit(1)
+}
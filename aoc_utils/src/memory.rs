@@ -0,0 +1,201 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::Serialize;
+
+/// Global allocator that counts allocations, installed with the `count-allocations` feature
+///
+/// Reallocations count as allocations of the new size.
+#[derive(Debug, Default)]
+pub struct CountingAllocator {
+    allocations: AtomicU64,
+    allocated: AtomicU64,
+    live: AtomicU64,
+    peak: AtomicU64,
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+
+impl CountingAllocator {
+    /// Creates an allocator without any allocations counted
+    pub const fn new() -> Self {
+        CountingAllocator {
+            allocations: AtomicU64::new(0),
+            allocated: AtomicU64::new(0),
+            live: AtomicU64::new(0),
+            peak: AtomicU64::new(0),
+        }
+    }
+
+    /// Runs `f` and returns the allocations made meanwhile
+    ///
+    /// The counters are shared by all threads, so allocations of other threads are included.
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, AllocStats) {
+        let allocations = self.allocations.load(Ordering::Relaxed);
+        let allocated = self.allocated.load(Ordering::Relaxed);
+        let live = self.live.load(Ordering::Relaxed);
+        self.peak.store(live, Ordering::Relaxed);
+
+        let result = f();
+        let stats = AllocStats {
+            allocations: self.allocations.load(Ordering::Relaxed) - allocations,
+            bytes: self.allocated.load(Ordering::Relaxed) - allocated,
+            peak_bytes: self.peak.load(Ordering::Relaxed).saturating_sub(live),
+        };
+        (result, stats)
+    }
+
+    fn add(&self, size: usize) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.allocated.fetch_add(size as u64, Ordering::Relaxed);
+        let live = self.live.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        self.peak.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn remove(&self, size: usize) {
+        self.live.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.remove(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.remove(layout.size());
+            self.add(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations of a step of a solution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// Number of allocations (including reallocations)
+    pub allocations: u64,
+    /// Total size of all allocations
+    pub bytes: u64,
+    /// Maximum size of the memory allocated at the same time, not counting memory that was already allocated before
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>9} allocs  {:>10} allocated  {:>10} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Size with a binary unit, e.g. `1.50 KiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.2} {}", size, UNITS[unit])
+}
+
+/// Whether allocations are counted, i.e. `aoc_utils` was built with the `count-allocations` feature
+pub fn counting_allocations() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// Runs `f` and returns the allocations made meanwhile, `None` if allocations are not counted
+pub fn measure_allocations<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "count-allocations")]
+    {
+        let (result, stats) = ALLOCATOR.measure(f);
+        (result, Some(stats))
+    }
+
+    #[cfg(not(feature = "count-allocations"))]
+    (f(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count() {
+        let allocator = CountingAllocator::new();
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let large = Layout::from_size_align(1000, 8).unwrap();
+        let ((), stats) = allocator.measure(|| unsafe {
+            let a = allocator.alloc(small);
+            let b = allocator.alloc_zeroed(large);
+            allocator.dealloc(b, large);
+            let a = allocator.realloc(a, small, 300);
+            allocator.dealloc(a, Layout::from_size_align(300, 8).unwrap());
+        });
+        assert_eq!(
+            AllocStats {
+                allocations: 3,
+                bytes: 1400,
+                peak_bytes: 1100
+            },
+            stats
+        );
+
+        // Memory allocated before the measurement does not count towards the peak
+        let kept = unsafe { allocator.alloc(large) };
+        let ((), stats) = allocator.measure(|| unsafe { allocator.dealloc(allocator.alloc(small), small) });
+        assert_eq!((1, 100, 100), (stats.allocations, stats.bytes, stats.peak_bytes));
+        unsafe { allocator.dealloc(kept, large) };
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.50 KiB", format_bytes(1536));
+        assert_eq!("3.00 MiB", format_bytes(3 * 1024 * 1024));
+        assert_eq!(
+            "        2 allocs        10 B allocated    2.00 KiB peak",
+            AllocStats {
+                allocations: 2,
+                bytes: 10,
+                peak_bytes: 2048
+            }
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    env, fmt,
+    io::{self, IsTerminal, Write},
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use serde_json::json;
+use termion::style;
+
+use crate::{Answers, Part};
+
+/// Environment variable that disables colored output if set to a non-empty value (see <https://no-color.org>)
+pub const NO_COLOR_ENV: &str = "NO_COLOR";
+
+const COLOR_AUTO: u8 = 0;
+const COLOR_OFF: u8 = 1;
+const COLOR_ON: u8 = 2;
+
+static COLOR: AtomicU8 = AtomicU8::new(COLOR_AUTO);
+
+/// Whether colors are allowed for the given stream based on `NO_COLOR` and whether it is a terminal
+fn detect_color(is_terminal: bool) -> bool {
+    is_terminal && env::var_os(NO_COLOR_ENV).is_none_or(|v| v.is_empty())
+}
+
+/// Whether colored output is written to stdout
+///
+/// Detected automatically (stdout is a terminal and `NO_COLOR` is not set) unless overridden with [`set_color_enabled`].
+pub fn color_enabled() -> bool {
+    match COLOR.load(Ordering::Relaxed) {
+        COLOR_OFF => false,
+        COLOR_ON => true,
+        _ => detect_color(io::stdout().is_terminal()),
+    }
+}
+
+/// Forces colored output on or off
+pub fn set_color_enabled(enabled: bool) {
+    COLOR.store(if enabled { COLOR_ON } else { COLOR_OFF }, Ordering::Relaxed);
+}
+
+/// Whether colored output is written to stderr
+pub fn stderr_color_enabled() -> bool {
+    detect_color(io::stderr().is_terminal())
+}
+
+/// Wraps the text in the given termion escape sequence(s) if colors are enabled
+pub fn paint(text: impl fmt::Display, escape: impl fmt::Display) -> String {
+    if color_enabled() {
+        format!("{}{}{}", escape, text, style::Reset)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Format in which the answers are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Day headers and answers, colored
+    Human,
+    /// Like [`OutputFormat::Human`], but without colors
+    Plain,
+    /// One JSON object per day and line
+    JsonLines,
+    /// CSV with header line
+    Csv,
+    /// Markdown table
+    Markdown,
+}
+
+impl OutputFormat {
+    /// Selects [`OutputFormat::Human`] if colors are enabled, otherwise [`OutputFormat::Plain`]
+    pub fn detect() -> Self {
+        if color_enabled() {
+            OutputFormat::Human
+        } else {
+            OutputFormat::Plain
+        }
+    }
+
+    /// Whether the format is meant to be read by people (as opposed to scripts)
+    pub fn is_human(&self) -> bool {
+        matches!(self, OutputFormat::Human | OutputFormat::Plain)
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "plain" => Ok(OutputFormat::Plain),
+            "json" | "jsonl" => Ok(OutputFormat::JsonLines),
+            "csv" => Ok(OutputFormat::Csv),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            _ => Err(format!("invalid format '{}', expected human, plain, json, csv or markdown", s)),
+        }
+    }
+}
+
+/// Writes answers in the selected [`OutputFormat`]
+///
+/// Human formats print the day header in [`ResultPrinter::begin_day`] so that it is visible while a day is
+/// being solved, machine-readable formats write one record per day in [`ResultPrinter::answers`].
+pub struct ResultPrinter<W: Write> {
+    format: OutputFormat,
+    out: W,
+    header_written: bool,
+}
+
+impl ResultPrinter<io::Stdout> {
+    /// Creates a printer writing to stdout
+    ///
+    /// Colors are enabled for [`OutputFormat::Human`] and disabled for all other formats.
+    pub fn stdout(format: OutputFormat) -> Self {
+        set_color_enabled(format == OutputFormat::Human);
+        Self::new(format, io::stdout())
+    }
+}
+
+impl<W: Write> ResultPrinter<W> {
+    /// Creates a printer writing to the given writer
+    pub fn new(format: OutputFormat, out: W) -> Self {
+        ResultPrinter { format, out, header_written: false }
+    }
+
+    /// Selected output format
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Called before a day is solved
+    pub fn begin_day(&mut self, day: i32) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Human => writeln!(self.out, "{}", day_header(day))?,
+            OutputFormat::Plain => writeln!(self.out, "Day {}:", day)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Writes the answers of a day
+    pub fn answers(&mut self, day: i32, answers: &Answers) -> io::Result<()> {
+        let answer = |part| answers.iter().find(|(p, _)| *p == part).map(|(_, a)| a);
+        match self.format {
+            OutputFormat::Human | OutputFormat::Plain => {
+                for (part, answer) in answers.iter() {
+                    writeln!(self.out, "  Result Star {}: {}", part.number(), answer)?;
+                }
+            }
+            OutputFormat::JsonLines => {
+                writeln!(self.out, "{}", json!({ "day": day, "star1": answer(Part::One), "star2": answer(Part::Two) }))?;
+            }
+            OutputFormat::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "day,star1,star2")?;
+                }
+
+                writeln!(self.out, "{},{},{}", day, csv_field(answer(Part::One)), csv_field(answer(Part::Two)))?;
+            }
+            OutputFormat::Markdown => {
+                if !self.header_written {
+                    writeln!(self.out, "| Day | Star 1 | Star 2 |")?;
+                    writeln!(self.out, "| --: | -----: | -----: |")?;
+                }
+
+                writeln!(self.out, "| {} | {} | {} |", day, markdown_cell(answer(Part::One)), markdown_cell(answer(Part::Two)))?;
+            }
+        }
+
+        self.header_written = true;
+        self.out.flush()
+    }
+
+    /// Consumes the printer and returns the writer
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+/// Day header as printed by [`crate::print_day_header`]
+pub(crate) fn day_header(day: i32) -> String {
+    paint(format!("Day {}:", day), format!("{}{}", style::Bold, termion::color::Fg(termion::color::Yellow)))
+}
+
+fn csv_field(value: Option<&str>) -> String {
+    match value {
+        Some(v) if v.contains([',', '"', '\n', '\r']) => format!("\"{}\"", v.replace('"', "\"\"")),
+        Some(v) => v.to_string(),
+        None => String::new(),
+    }
+}
+
+fn markdown_cell(value: Option<&str>) -> String {
+    value.map_or("-".to_string(), |v| v.replace('|', "\\|").replace('\n', "<br>"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(part1: &str, part2: Option<&str>) -> Answers {
+        Answers {
+            part1: Some(part1.to_string()),
+            part2: part2.map(str::to_string),
+        }
+    }
+
+    fn print(format: OutputFormat) -> String {
+        let mut printer = ResultPrinter::new(format, Vec::new());
+        printer.begin_day(1).unwrap();
+        printer.answers(1, &answers("1301", Some("1346"))).unwrap();
+        printer.begin_day(25).unwrap();
+        printer.answers(25, &answers("a,\"b\"", None)).unwrap();
+        String::from_utf8(printer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(Ok(OutputFormat::JsonLines), "json".parse());
+        assert_eq!(Ok(OutputFormat::Markdown), "md".parse());
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn no_color_without_terminal() {
+        assert!(!detect_color(false));
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!("Day 1:\n  Result Star 1: 1301\n  Result Star 2: 1346\nDay 25:\n  Result Star 1: a,\"b\"\n", print(OutputFormat::Plain));
+    }
+
+    #[test]
+    fn json_lines() {
+        let output = print(OutputFormat::JsonLines);
+        let lines: Vec<serde_json::Value> = output.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(2, lines.len());
+        assert_eq!(json!({ "day": 1, "star1": "1301", "star2": "1346" }), lines[0]);
+        assert!(lines[1]["star2"].is_null());
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!("day,star1,star2\n1,1301,1346\n25,\"a,\"\"b\"\"\",\n", print(OutputFormat::Csv));
+    }
+
+    #[test]
+    fn markdown() {
+        let output = print(OutputFormat::Markdown);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!("| Day | Star 1 | Star 2 |", lines[0]);
+        assert_eq!("| 1 | 1301 | 1346 |", lines[2]);
+        assert_eq!("| 25 | a,\"b\" | - |", lines[3]);
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    any::Any,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use termion::{color, style};
+
+use crate::{paint, try_read_input, Answers, Budget, DynSolution, InputError, InputSource, Part};
+
+/// How a day run by [`run_parallel`] ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayStatus {
+    Ok,
+    /// Input cannot be read or parsed
+    Failed(String),
+    /// Solution panicked with the given message
+    Panicked(String),
+    /// Solution did not finish within the timeout, with the best result it reported so far (see [`Budget::report_best`])
+    TimedOut(Option<String>),
+}
+
+impl DayStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            DayStatus::Ok => "ok",
+            DayStatus::Failed(_) => "failed",
+            DayStatus::Panicked(_) => "panicked",
+            DayStatus::TimedOut(_) => "timed out",
+        }
+    }
+}
+
+/// Result of a day run by [`run_parallel`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: i32,
+    pub status: DayStatus,
+    /// Empty unless the day finished successfully
+    pub answers: Answers,
+    /// Wall-clock time including reading the input (the timeout if the day timed out)
+    pub elapsed: Duration,
+}
+
+/// Solves the given days concurrently on `threads` worker threads
+///
+/// Every day runs on its own thread so that a panic only ends that day. A day exceeding the timeout is
+/// abandoned and its [`Budget`] is cancelled; solvers that do not check their budget keep running in the
+/// background until the process exits. The reports are ordered by day.
+pub fn run_parallel(jobs: Vec<(&'static dyn DynSolution, InputSource)>, part: Option<Part>, threads: usize, timeout: Option<Duration>) -> Vec<DayReport> {
+    let queue = Mutex::new(jobs.into_iter());
+    let reports = Mutex::new(Vec::new());
+    thread::scope(|s| {
+        for _ in 0..threads.max(1) {
+            s.spawn(|| loop {
+                let Some((solution, input)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let report = run_day(solution, input, part, timeout);
+                reports.lock().unwrap().push(report);
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|r| r.day);
+    reports
+}
+
+fn run_day(solution: &'static dyn DynSolution, input: InputSource, part: Option<Part>, timeout: Option<Duration>) -> DayReport {
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+    let budget = timeout.map_or_else(Budget::unlimited, Budget::with_timeout);
+    let solver_budget = budget.clone();
+    thread::Builder::new()
+        .name(format!("day{:0>2}", solution.day()))
+        .spawn(move || {
+            let result = solver_budget.install(|| {
+                panic::catch_unwind(AssertUnwindSafe(|| {
+                    let content = try_read_input(input.clone())?;
+                    solution.solve(&content, part).map_err(|e| InputError::Parse(input, e))
+                }))
+            });
+            let _ = tx.send(result);
+        })
+        .expect("failed to spawn thread");
+
+    let result = rx.recv_timeout(timeout.unwrap_or(Duration::MAX));
+    // Whatever a solver returns after its budget ran out (even a panic) is not a valid answer
+    let timed_out = budget.is_exhausted();
+    budget.cancel();
+    let (status, answers) = match result {
+        _ if timed_out => (DayStatus::TimedOut(budget.best()), Answers::default()),
+        Ok(Ok(Ok(answers))) => (DayStatus::Ok, answers),
+        Ok(Ok(Err(e))) => (DayStatus::Failed(e.to_string()), Answers::default()),
+        Ok(Err(payload)) => (DayStatus::Panicked(panic_message(payload.as_ref())), Answers::default()),
+        Err(_) => (DayStatus::Panicked("thread ended without result".to_string()), Answers::default()),
+    };
+
+    DayReport {
+        day: solution.day(),
+        status,
+        answers,
+        elapsed: start.elapsed(),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "unknown panic".to_string(),
+    }
+}
+
+/// Writes the reports as a table with status, answers and time per day, followed by the errors
+pub fn write_summary<W: Write>(out: &mut W, reports: &[DayReport]) -> io::Result<()> {
+    let answer = |report: &DayReport, part| report.answers.iter().find(|(p, _)| *p == part).map_or("-".to_string(), |(_, a)| a.to_string());
+    let width = |part| reports.iter().map(|r| answer(r, part).chars().count()).chain([6]).max().unwrap_or_default();
+    let (width1, width2) = (width(Part::One), width(Part::Two));
+
+    writeln!(out, "{}", paint(format!("Day  {:<9}  {:<width1$}  {:<width2$}  Time", "Status", "Star 1", "Star 2"), style::Bold))?;
+    for report in reports {
+        let status = format!("{:<9}", report.status.label());
+        let status = match report.status {
+            DayStatus::Ok => paint(status, color::Fg(color::Green)),
+            _ => paint(status, format!("{}{}", style::Bold, color::Fg(color::Red))),
+        };
+        writeln!(
+            out,
+            "{:>3}  {}  {:<width1$}  {:<width2$}  {:.2?}",
+            report.day,
+            status,
+            answer(report, Part::One),
+            answer(report, Part::Two),
+            report.elapsed
+        )?;
+    }
+
+    let mut failed = 0;
+    for report in reports {
+        match &report.status {
+            DayStatus::Ok => continue,
+            DayStatus::Failed(message) | DayStatus::Panicked(message) => writeln!(out, "Day {} {}: {}", report.day, report.status.label(), message)?,
+            DayStatus::TimedOut(None) => writeln!(out, "Day {} timed out after {:.2?}", report.day, report.elapsed)?,
+            DayStatus::TimedOut(Some(best)) => writeln!(out, "Day {} timed out after {:.2?}, best result so far: {}", report.day, report.elapsed, best)?,
+        }
+
+        failed += 1;
+    }
+
+    writeln!(out, "\n{} ok, {} failed", reports.len() - failed, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoAnswer, ParseError, Solution};
+    use std::{env, fs, path::PathBuf};
+
+    macro_rules! solution {
+        ($name:ident, $day:literal, $part1:expr) => {
+            struct $name;
+
+            impl Solution for $name {
+                const DAY: i32 = $day;
+                const HAS_PART2: bool = false;
+                type Input = usize;
+                type Answer1 = usize;
+                type Answer2 = NoAnswer;
+
+                fn parse(input: &str) -> Result<Self::Input, ParseError> {
+                    Ok(input.lines().count())
+                }
+
+                fn part1(input: &Self::Input) -> Self::Answer1 {
+                    $part1(*input)
+                }
+
+                fn part2(_: &Self::Input) -> Self::Answer2 {
+                    NoAnswer
+                }
+            }
+        };
+    }
+
+    solution!(Lines, 1, |lines| lines);
+    solution!(Panics, 19, |_| -> usize { panic!("No overlap found!") });
+    solution!(Sleeps, 23, |lines| {
+        thread::sleep(Duration::from_secs(2));
+        lines
+    });
+
+    solution!(Cooperates, 24, |lines| {
+        let budget = Budget::current();
+        let mut best = 0;
+        while !budget.is_exhausted() {
+            best = lines;
+            budget.report_best(best);
+            thread::sleep(Duration::from_millis(1));
+        }
+        best
+    });
+
+    fn input_file(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-parallel-{}-{}.txt", std::process::id(), name));
+        fs::write(&path, "a\nb\nc\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn isolate_failures() {
+        let path = input_file("isolate_failures");
+        let input = InputSource::File(path.clone());
+        let missing = InputSource::File(path.with_extension("missing"));
+        let jobs: Vec<(&'static dyn DynSolution, InputSource)> = vec![(&Sleeps, input.clone()), (&Panics, input.clone()), (&Lines, missing), (&Lines, input)];
+
+        let reports = run_parallel(jobs, None, 2, Some(Duration::from_millis(200)));
+        fs::remove_file(&path).unwrap();
+        let mut statuses: Vec<_> = reports.iter().map(|r| (r.day, r.status.label())).collect();
+        statuses.sort();
+        assert_eq!(vec![(1, "failed"), (1, "ok"), (19, "panicked"), (23, "timed out")], statuses);
+
+        let ok = reports.iter().find(|r| r.status == DayStatus::Ok).unwrap();
+        assert_eq!(Some("3".to_string()), ok.answers.part1);
+        assert_eq!(DayStatus::Panicked("No overlap found!".to_string()), reports[2].status);
+        assert!(reports[3].elapsed < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn cooperative_timeout() {
+        let path = input_file("cooperative_timeout");
+        let reports = run_parallel(vec![(&Cooperates, InputSource::File(path.clone()))], None, 1, Some(Duration::from_millis(50)));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(DayStatus::TimedOut(Some("3".to_string())), reports[0].status);
+        assert_eq!(Answers::default(), reports[0].answers);
+    }
+
+    #[test]
+    fn summary() {
+        let reports = vec![
+            DayReport {
+                day: 1,
+                status: DayStatus::Ok,
+                answers: Answers {
+                    part1: Some("1301".to_string()),
+                    part2: Some("1346".to_string()),
+                },
+                elapsed: Duration::from_millis(2),
+            },
+            DayReport {
+                day: 19,
+                status: DayStatus::Panicked("No overlap found!".to_string()),
+                answers: Answers::default(),
+                elapsed: Duration::from_millis(15),
+            },
+            DayReport {
+                day: 23,
+                status: DayStatus::TimedOut(Some("13495".to_string())),
+                answers: Answers::default(),
+                elapsed: Duration::from_secs(1),
+            },
+        ];
+
+        crate::set_color_enabled(false);
+        let mut out = Vec::new();
+        write_summary(&mut out, &reports).unwrap();
+        assert_eq!(
+            "Day  Status     Star 1  Star 2  Time\n  1  ok         1301    1346    2.00ms\n 19  panicked   -       -       15.00ms\n 23  timed out  -       -       1.00s\n\
+             Day 19 panicked: No overlap found!\nDay 23 timed out after 1.00s, best result so far: 13495\n\n1 ok, 2 failed\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::GridError;
+
+/// Error while parsing puzzle input, pointing to the offending line and column (both 1-based)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error at the given position
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<GridError> for ParseError {
+    fn from(err: GridError) -> Self {
+        match err {
+            GridError::Empty => ParseError::new(1, 1, err.to_string()),
+            GridError::UnevenRow { row, .. } => ParseError::new(row, 1, err.to_string()),
+            GridError::InvalidCell { row, col, .. } => ParseError::new(row, col, err.to_string()),
+        }
+    }
+}
+
+/// Cursor over a single line of input
+///
+/// The methods consume the expected token and return it, or fail with a [`ParseError`] pointing at the
+/// current column. A typical line parser reads like the line format itself:
+///
+/// ```
+/// # use aoc_utils::{parse_lines, ParseError};
+/// let points = parse_lines("0,9\n8,0", |l| {
+///     let x = l.number::<i32>()?;
+///     l.literal(",")?;
+///     Ok((x, l.number::<i32>()?))
+/// })?;
+/// assert_eq!(vec![(0, 9), (8, 0)], points);
+/// # Ok::<(), ParseError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineParser<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> LineParser<'a> {
+    /// Creates a parser for the given text; `line` is the 1-based line number used in errors
+    pub fn new(line: usize, text: &'a str) -> Self {
+        LineParser { line, text, pos: 0 }
+    }
+
+    /// Line number (1-based)
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Current column (1-based, counted in characters)
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    /// Text that has not been consumed yet
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// Whether the whole line has been consumed
+    pub fn is_at_end(&self) -> bool {
+        self.pos == self.text.len()
+    }
+
+    /// Creates an error at the current column
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, self.column(), message)
+    }
+
+    /// Creates an "expected ..., found ..." error at the current column
+    pub fn expected(&self, what: impl fmt::Display) -> ParseError {
+        self.error(format!("expected {}, found {}", what, self.found()))
+    }
+
+    fn found(&self) -> String {
+        match self.rest().split_whitespace().next() {
+            Some(token) => format!("'{}'", token),
+            None => "end of line".to_string(),
+        }
+    }
+
+    /// Consumes the given literal text
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.try_literal(literal) {
+            Ok(())
+        } else {
+            Err(self.expected(format_args!("'{}'", literal)))
+        }
+    }
+
+    /// Consumes the given literal text if it follows, returns whether it did
+    pub fn try_literal(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+
+        found
+    }
+
+    /// Skips spaces and tabs
+    pub fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    }
+
+    /// Consumes a non-empty run of chars matching the predicate; `what` describes the token in errors
+    pub fn take_while(&mut self, what: &str, pred: impl Fn(char) -> bool) -> Result<&'a str, ParseError> {
+        let rest = self.rest();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.expected(what));
+        }
+
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// Consumes a word (letters, digits and underscores)
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        self.take_while("word", |c| c.is_alphanumeric() || c == '_')
+    }
+
+    /// Consumes an optionally signed decimal number
+    pub fn number<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let rest = self.rest();
+        let sign = if rest.starts_with(['-', '+']) { 1 } else { 0 };
+        let digits = rest[sign..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len() - sign);
+        if digits == 0 {
+            return Err(self.expected("number"));
+        }
+
+        let token = &rest[..sign + digits];
+        let value = token.parse().map_err(|e| self.error(format!("invalid number '{}': {}", token, e)))?;
+        self.pos += token.len();
+        Ok(value)
+    }
+
+    /// Consumes one of the given keywords and returns the associated value
+    pub fn one_of<T: Copy>(&mut self, options: &[(&str, T)]) -> Result<T, ParseError> {
+        // Longest match first so that e.g. "on" does not shadow "once"
+        let mut matches: Vec<_> = options.iter().filter(|(keyword, _)| self.rest().starts_with(keyword)).collect();
+        matches.sort_by_key(|(keyword, _)| std::cmp::Reverse(keyword.len()));
+        match matches.first() {
+            Some((keyword, value)) => {
+                self.pos += keyword.len();
+                Ok(*value)
+            }
+            None => {
+                let keywords: Vec<_> = options.iter().map(|(keyword, _)| format!("'{}'", keyword)).collect();
+                Err(self.expected(keywords.join(" or ")))
+            }
+        }
+    }
+
+    /// Parses items separated by `separator` (at least one item)
+    pub fn separated<T>(&mut self, separator: &str, mut item: impl FnMut(&mut Self) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+        while self.try_literal(separator) {
+            items.push(item(self)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Fails if there is unconsumed text (except trailing whitespace)
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.rest().trim_end().is_empty() {
+            Ok(())
+        } else {
+            Err(self.expected("end of line"))
+        }
+    }
+}
+
+/// Iterates over the non-empty lines of the input with their line numbers
+///
+/// A trailing `\r` (Windows line endings) is not part of the line.
+pub fn input_lines(input: &str) -> impl Iterator<Item = LineParser<'_>> {
+    input
+        .split('\n')
+        .enumerate()
+        .map(|(ix, line)| LineParser::new(ix + 1, line.strip_suffix('\r').unwrap_or(line)))
+        .filter(|line| !line.rest().trim().is_empty())
+}
+
+/// Parses each non-empty line of the input with the given line parser
+///
+/// Fails with the first error, also if the line parser does not consume the whole line.
+pub fn parse_lines<'a, T>(input: &'a str, mut f: impl FnMut(&mut LineParser<'a>) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+    input_lines(input)
+        .map(|mut line| {
+            let value = f(&mut line)?;
+            line.end()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Parses an input that consists of a single line (e.g. a comma separated list)
+pub fn parse_line<'a, T>(input: &'a str, f: impl FnMut(&mut LineParser<'a>) -> Result<T, ParseError>) -> Result<T, ParseError> {
+    let mut values = parse_lines(input, f)?.into_iter();
+    match (values.next(), input_lines(input).nth(1)) {
+        (Some(value), None) => Ok(value),
+        (None, _) => Err(ParseError::new(1, 1, "input is empty")),
+        (Some(_), Some(line)) => Err(line.error("expected a single line")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fields() {
+        let mut line = LineParser::new(1, "on x=-5..12,y=3");
+        assert!(line.one_of(&[("on", true), ("off", false)]).unwrap());
+        line.skip_whitespace();
+        line.literal("x=").unwrap();
+        assert_eq!(-5, line.number::<i32>().unwrap());
+        line.literal("..").unwrap();
+        assert_eq!(12, line.number::<i32>().unwrap());
+        assert!(line.try_literal(",y="));
+        assert_eq!(3, line.number::<u8>().unwrap());
+        assert!(line.is_at_end());
+    }
+
+    #[test]
+    fn error_positions() {
+        let err = parse_lines("forward 5\n\nforward x", |l| {
+            Ok((l.word()?.to_string(), {
+                l.literal(" ")?;
+                l.number::<u32>()?
+            }))
+        })
+        .unwrap_err();
+        assert_eq!(ParseError::new(3, 9, "expected number, found 'x'"), err);
+        assert_eq!("line 3, column 9: expected number, found 'x'", err.to_string());
+
+        let err = parse_line("1,2..", |l| l.separated(",", LineParser::number::<i32>)).unwrap_err();
+        assert_eq!(ParseError::new(1, 4, "expected end of line, found '..'"), err);
+
+        let err = LineParser::new(1, "300").number::<u8>().unwrap_err();
+        assert!(err.message.starts_with("invalid number '300'"));
+    }
+
+    #[test]
+    fn keywords() {
+        let mut line = LineParser::new(2, "sideways");
+        let err = line.one_of(&[("up", 0), ("down", 1)]).unwrap_err();
+        assert_eq!("line 2, column 1: expected 'up' or 'down', found 'sideways'", err.to_string());
+        assert_eq!(Ok("sideways"), line.word());
+        assert_eq!(Err(ParseError::new(2, 9, "expected word, found end of line")), line.word());
+    }
+
+    #[test]
+    fn single_line() {
+        assert_eq!(Ok(vec![3, 4, 3]), parse_line("3,4,3\r\n", |l| l.separated(",", LineParser::number::<u8>)));
+        assert_eq!(Err(ParseError::new(2, 1, "expected a single line")), parse_line("1\n2", LineParser::number::<u8>));
+        assert!(parse_line("\n", LineParser::number::<u8>).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    env,
+    error::Error,
+    fmt,
+    io::Write,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    exit_with_error, init_tracing, open_input, read_lines, split_trace_args, try_read_input, Answers, Budget, DynSolution, InputError, InputSource, OutputFormat, Part, ResultPrinter,
+    StreamingSolution,
+};
+
+/// Days selected on the command line of the runner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    /// All days (`all`)
+    All,
+    /// A single day (`16`) or a range of days (`19..=25` or `19..26`)
+    Range(RangeInclusive<i32>),
+}
+
+impl DaySelection {
+    /// Checks whether the given day is selected
+    pub fn contains(&self, day: i32) -> bool {
+        match self {
+            DaySelection::All => true,
+            DaySelection::Range(r) => r.contains(&day),
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_day(s: &str, valid: RangeInclusive<i32>) -> Result<i32, String> {
+            match s.trim().parse() {
+                Ok(day) if valid.contains(&day) => Ok(day),
+                _ => Err(format!("invalid day '{}', expected a number between {} and {}", s, valid.start(), valid.end())),
+            }
+        }
+
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+
+        let range = if let Some((from, to)) = s.split_once("..=") {
+            parse_day(from, 1..=25)?..=parse_day(to, 1..=25)?
+        } else if let Some((from, to)) = s.split_once("..") {
+            parse_day(from, 1..=25)?..=parse_day(to, 1..=26)? - 1
+        } else {
+            let day = parse_day(s, 1..=25)?;
+            day..=day
+        };
+
+        if range.is_empty() {
+            return Err(format!("day range '{}' is empty", s));
+        }
+
+        Ok(DaySelection::Range(range))
+    }
+}
+
+/// Why [`run_solution`] did not return answers
+#[derive(Debug)]
+pub enum RunError {
+    /// Input cannot be read or parsed
+    Input(InputError),
+    /// Solution did not finish within the timeout, with the best result it reported so far (see [`Budget::report_best`])
+    TimedOut { elapsed: Duration, best: Option<String> },
+}
+
+impl From<InputError> for RunError {
+    fn from(err: InputError) -> Self {
+        RunError::Input(err)
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Input(err) => write!(f, "{}", err),
+            RunError::TimedOut { elapsed, best: None } => write!(f, "timed out after {:.2?}", elapsed),
+            RunError::TimedOut { elapsed, best: Some(best) } => write!(f, "timed out after {:.2?}, best result so far: {}", elapsed, best),
+        }
+    }
+}
+
+impl Error for RunError {}
+
+/// Reads the input for a solution, solves the selected part(s) and prints the answers with the given printer
+///
+/// The solution runs with a [`Budget`] of `timeout`. Its answers are discarded if the budget ran out, as
+/// solvers checking it stop early. Panics if the answers cannot be written (like `println!`).
+pub fn run_solution<W: Write>(solution: &dyn DynSolution, input: InputSource, part: Option<Part>, timeout: Option<Duration>, printer: &mut ResultPrinter<W>) -> Result<Answers, RunError> {
+    printer.begin_day(solution.day()).expect("failed to write output");
+    let start = Instant::now();
+    let budget = timeout.map_or_else(Budget::unlimited, Budget::with_timeout);
+    let result = budget.install(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let content = try_read_input(input.clone())?;
+            solution.solve(&content, part).map_err(|e| InputError::Parse(input, e))
+        }))
+    });
+
+    // Whatever a solver returns after its budget ran out (even a panic) is not a valid answer
+    if budget.is_exhausted() {
+        return Err(RunError::TimedOut {
+            elapsed: start.elapsed(),
+            best: budget.best(),
+        });
+    }
+
+    let answers = result.unwrap_or_else(|payload| panic::resume_unwind(payload))?;
+    printer.answers(solution.day(), &answers).expect("failed to write output");
+    Ok(answers)
+}
+
+/// Entry point for the binaries of the days
+///
+/// Solves both parts with the input selected on the command line (see [`InputSource::for_day`]), traced if
+/// requested with `--trace` or [`TRACE_ENV`](crate::TRACE_ENV).
+pub fn run_main(solution: &dyn DynSolution) {
+    init_tracing_from_args();
+    let mut printer = ResultPrinter::stdout(OutputFormat::detect());
+    if let Err(e) = run_solution(solution, InputSource::for_day(solution.day()), None, None, &mut printer) {
+        exit_with_error(&e);
+    }
+}
+
+/// Starts tracing if requested on the command line of the current process (see [`split_trace_args`])
+fn init_tracing_from_args() {
+    if let (_, Some(output)) = split_trace_args(env::args().skip(1).collect()) {
+        init_tracing(output).unwrap_or_else(|e| exit_with_error(&e));
+    }
+}
+
+/// Solves both parts of a [`StreamingSolution`] while reading the input line by line
+pub fn solve_streaming<S: StreamingSolution>(input: InputSource) -> Result<Answers, InputError> {
+    let _span = crate::trace_span!("solve", day = S::DAY);
+    let reader = open_input(input.clone())?;
+    let (mut error, mut empty) = (None, true);
+    let lines = read_lines(reader).map_while(|line| {
+        empty = false;
+        line.map_err(|e| error = Some(e)).ok()
+    });
+
+    let result = S::solve_lines(lines);
+    match (error, result) {
+        (Some(e), _) => Err(InputError::from_io(input, e)),
+        (None, _) if empty => Err(InputError::Empty(input)),
+        (None, Err(e)) => Err(InputError::Parse(input, e)),
+        (None, Ok((part1, part2))) => Ok(Answers {
+            part1: Some(part1.to_string()),
+            part2: S::HAS_PART2.then(|| part2.to_string()),
+        }),
+    }
+}
+
+/// Entry point for the binaries of days with a [`StreamingSolution`]
+///
+/// Like [`run_main`], but the input is not read into memory, so it can be arbitrarily large.
+pub fn run_streaming_main<S: StreamingSolution>() {
+    init_tracing_from_args();
+    let mut printer = ResultPrinter::stdout(OutputFormat::detect());
+    printer.begin_day(S::DAY).expect("failed to write output");
+    match solve_streaming::<S>(InputSource::for_day(S::DAY)) {
+        Ok(answers) => printer.answers(S::DAY, &answers).expect("failed to write output"),
+        Err(e) => exit_with_error(&e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, LineParser, NoAnswer, ParseError, Solution};
+    use std::{env, fs, path::Path, thread};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: i32 = 99;
+        const HAS_PART2: bool = false;
+        type Input = Vec<i32>;
+        type Answer1 = i32;
+        type Answer2 = NoAnswer;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            parse_lines(input, |line| line.number())
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            input.iter().sum()
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            NoAnswer
+        }
+    }
+
+    impl StreamingSolution for Sum {
+        fn solve_lines(lines: impl Iterator<Item = (usize, String)>) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+            let mut sum = 0;
+            for (number, line) in lines {
+                let mut line = LineParser::new(number, &line);
+                sum += line.number::<i32>()?;
+                line.end()?;
+            }
+
+            Ok((sum, NoAnswer))
+        }
+    }
+
+    /// Searches until its budget is exhausted, like day 24
+    struct Searches;
+
+    impl Solution for Searches {
+        const DAY: i32 = 24;
+        const HAS_PART2: bool = false;
+        type Input = ();
+        type Answer1 = i32;
+        type Answer2 = NoAnswer;
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Self::Answer1 {
+            let budget = Budget::current();
+            budget.report_best(42);
+            while !budget.is_exhausted() {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            panic!("search was cancelled")
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            NoAnswer
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let path = env::temp_dir().join(format!("aoc-runner-{}-timeout.txt", std::process::id()));
+        fs::write(&path, "1\n2").unwrap();
+        let mut printer = ResultPrinter::new(OutputFormat::Plain, Vec::new());
+        let timed_out = run_solution(&Searches, InputSource::File(path.clone()), None, Some(Duration::from_millis(50)), &mut printer);
+        let answers = run_solution(&Sum, InputSource::File(path.clone()), None, Some(Duration::from_secs(60)), &mut printer);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(&timed_out, Err(RunError::TimedOut { best: Some(best), .. }) if best == "42"));
+        assert!(timed_out.unwrap_err().to_string().ends_with(", best result so far: 42"));
+        assert_eq!(Some("3".to_string()), answers.unwrap().part1);
+    }
+
+    fn solve(name: &str, content: &str) -> Result<Answers, InputError> {
+        let path = env::temp_dir().join(format!("aoc-streaming-{}-{}.txt", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        let result = solve_streaming::<Sum>(InputSource::File(path.clone()));
+        fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn streaming() {
+        assert_eq!(
+            Answers {
+                part1: Some("6".to_string()),
+                part2: None
+            },
+            solve("valid", "1\r\n2\n\n3").unwrap()
+        );
+        assert_eq!(Sum.solve("1\r\n2\n\n3", None).unwrap(), solve("same", "1\r\n2\n\n3").unwrap());
+        assert!(matches!(solve("invalid", "1\n\nx\n"), Err(InputError::Parse(_, e)) if e.line == 3));
+        assert!(matches!(solve("empty", "\n \n"), Err(InputError::Empty(_))));
+        assert!(matches!(solve_streaming::<Sum>(InputSource::File(Path::new("day42").join("input.txt"))), Err(InputError::NotFound(_))));
+    }
+
+    #[test]
+    fn select_all() {
+        let selection: DaySelection = "all".parse().unwrap();
+        assert!(selection.contains(1));
+        assert!(selection.contains(25));
+    }
+
+    #[test]
+    fn select_single_day() {
+        assert_eq!(Ok(DaySelection::Range(16..=16)), "16".parse());
+        assert_eq!(Ok(DaySelection::Range(1..=1)), "01".parse());
+    }
+
+    #[test]
+    fn select_range() {
+        assert_eq!(Ok(DaySelection::Range(19..=25)), "19..=25".parse());
+        assert_eq!(Ok(DaySelection::Range(19..=24)), "19..25".parse());
+        assert_eq!(Ok(DaySelection::Range(19..=25)), "19..26".parse());
+    }
+
+    #[test]
+    fn select_invalid() {
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("19..=26".parse::<DaySelection>().is_err());
+        assert!("x".parse::<DaySelection>().is_err());
+        assert!("20..=19".parse::<DaySelection>().is_err());
+        assert!("19..19".parse::<DaySelection>().is_err());
+    }
+}
@@ -0,0 +1,329 @@
+use std::{
+    fmt,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{measure_allocations, normalize_input, AllocStats, ParseError};
+
+/// Common interface of all puzzle solutions
+///
+/// Each day implements this trait on a unit struct (e.g. `day16::Day16`). The input is parsed
+/// once and shared by both parts.
+pub trait Solution {
+    /// Day of the puzzle
+    const DAY: i32;
+
+    /// Whether the puzzle has a second part (day 25 doesn't)
+    const HAS_PART2: bool = true;
+
+    /// Parsed puzzle input
+    type Input;
+
+    /// Answer of part 1
+    type Answer1: fmt::Display;
+
+    /// Answer of part 2, use [`NoAnswer`] if there is no second part
+    type Answer2: fmt::Display;
+
+    /// Parses the puzzle input
+    ///
+    /// Malformed input is reported with a [`ParseError`] instead of panicking. The runner passes the input
+    /// through [`normalize_input`] first, but parsers should accept `\r\n` line endings and a final line
+    /// break as well when they are called directly.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Solves part 1
+    fn part1(input: &Self::Input) -> Self::Answer1;
+
+    /// Solves part 2
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+/// Solution that solves both parts in a single pass over the lines of the input
+///
+/// Implemented by days whose answers can be computed without keeping the whole input in memory, so that
+/// [`crate::run_streaming_main`] can process inputs of any size (e.g. generated inputs piped through stdin).
+pub trait StreamingSolution: Solution {
+    /// Solves both parts from the non-empty lines of the input with their line numbers (see [`crate::read_lines`])
+    fn solve_lines(lines: impl Iterator<Item = (usize, String)>) -> Result<(Self::Answer1, Self::Answer2), ParseError>;
+}
+
+/// Answer for a part that does not exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl fmt::Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "-")
+    }
+}
+
+/// Part of a puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Number of the part (1 or 2)
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part '{}', expected 1 or 2", s)),
+        }
+    }
+}
+
+/// Answers of a day, formatted for display
+///
+/// A part is `None` if it was not selected or does not exist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    /// Iterates over the parts that have an answer
+    pub fn iter(&self) -> impl Iterator<Item = (Part, &str)> {
+        [(Part::One, &self.part1), (Part::Two, &self.part2)].into_iter().filter_map(|(p, a)| a.as_deref().map(|a| (p, a)))
+    }
+}
+
+/// Object safe version of [`Solution`]
+///
+/// Implemented for every [`Solution`] so that days can be collected in a list and run by the `aoc` runner.
+pub trait DynSolution: Sync {
+    /// Day of the puzzle
+    fn day(&self) -> i32;
+
+    /// Whether the puzzle has a second part
+    fn has_part2(&self) -> bool;
+
+    /// Normalizes and parses the input and solves the selected part (or all parts if `part` is `None`)
+    fn solve(&self, input: &str, part: Option<Part>) -> Result<Answers, ParseError>;
+
+    /// Parses the input and solves all parts, measuring how long each step takes
+    fn time(&self, input: &str) -> Result<Timings, ParseError>;
+
+    /// Parses the input and solves all parts, counting the allocations of each step
+    ///
+    /// Returns `None` if allocations are not counted (see [`counting_allocations`](crate::counting_allocations)).
+    fn allocations(&self, input: &str) -> Result<Option<Allocations>, ParseError>;
+}
+
+/// Durations of a single run of a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    /// `None` if the puzzle has no second part
+    pub part2: Option<Duration>,
+}
+
+/// Allocations of a single run of a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Allocations {
+    pub parse: AllocStats,
+    pub part1: AllocStats,
+    /// `None` if the puzzle has no second part
+    pub part2: Option<AllocStats>,
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> i32 {
+        S::DAY
+    }
+
+    fn has_part2(&self) -> bool {
+        S::HAS_PART2
+    }
+
+    fn solve(&self, input: &str, part: Option<Part>) -> Result<Answers, ParseError> {
+        let _span = crate::trace_span!("solve", day = S::DAY);
+        let input = {
+            let _span = crate::trace_span!("parse");
+            S::parse(&normalize_input(input))?
+        };
+
+        Ok(Answers {
+            part1: (part != Some(Part::Two)).then(|| {
+                let _span = crate::trace_span!("part1");
+                S::part1(&input).to_string()
+            }),
+            part2: (S::HAS_PART2 && part != Some(Part::One)).then(|| {
+                let _span = crate::trace_span!("part2");
+                S::part2(&input).to_string()
+            }),
+        })
+    }
+
+    fn time(&self, input: &str) -> Result<Timings, ParseError> {
+        fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+            let start = Instant::now();
+            let result = black_box(f());
+            (result, start.elapsed())
+        }
+
+        let input = normalize_input(input);
+        let (input, parse) = measure(|| S::parse(black_box(&input)));
+        let input = input?;
+        let (_, part1) = measure(|| S::part1(&input));
+        let part2 = S::HAS_PART2.then(|| measure(|| S::part2(&input)).1);
+        Ok(Timings { parse, part1, part2 })
+    }
+
+    fn allocations(&self, input: &str) -> Result<Option<Allocations>, ParseError> {
+        let input = normalize_input(input);
+        let (input, Some(parse)) = measure_allocations(|| S::parse(&input)) else {
+            return Ok(None);
+        };
+
+        let input = input?;
+        let part1 = measure_allocations(|| S::part1(&input)).1.unwrap_or_default();
+        let part2 = S::HAS_PART2.then(|| measure_allocations(|| S::part2(&input)).1.unwrap_or_default());
+        Ok(Some(Allocations { parse, part1, part2 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, LineParser};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: i32 = 99;
+        type Input = Vec<i32>;
+        type Answer1 = i32;
+        type Answer2 = i32;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            parse_lines(input, LineParser::number)
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Answer2 {
+            input.iter().product()
+        }
+    }
+
+    struct OnlyOnePart;
+
+    impl Solution for OnlyOnePart {
+        const DAY: i32 = 25;
+        const HAS_PART2: bool = false;
+        type Input = ();
+        type Answer1 = &'static str;
+        type Answer2 = NoAnswer;
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Self::Answer1 {
+            "done"
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            NoAnswer
+        }
+    }
+
+    struct Echo;
+
+    impl Solution for Echo {
+        const DAY: i32 = 98;
+        const HAS_PART2: bool = false;
+        type Input = String;
+        type Answer1 = String;
+        type Answer2 = NoAnswer;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            format!("{:?}", input)
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            NoAnswer
+        }
+    }
+
+    #[test]
+    fn solve_all_parts() {
+        let answers = Sum.solve("2\n3\n4", None).unwrap();
+        assert_eq!(Some("9"), answers.part1.as_deref());
+        assert_eq!(Some("24"), answers.part2.as_deref());
+    }
+
+    #[test]
+    fn solve_single_part() {
+        let answers = Sum.solve("2\n3\n4", Some(Part::Two)).unwrap();
+        assert_eq!(None, answers.part1);
+        assert_eq!(Some("24"), answers.part2.as_deref());
+        assert_eq!(vec![(Part::Two, "24")], answers.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn solve_without_part2() {
+        let solution: &dyn DynSolution = &OnlyOnePart;
+        assert!(!solution.has_part2());
+        let answers = solution.solve("", None).unwrap();
+        assert_eq!(Some("done"), answers.part1.as_deref());
+        assert_eq!(None, answers.part2);
+    }
+
+    #[test]
+    fn time_parts() {
+        let timings = Sum.time("2\n3\n4").unwrap();
+        assert!(timings.part2.is_some());
+
+        let timings = OnlyOnePart.time("").unwrap();
+        assert_eq!(None, timings.part2);
+    }
+
+    #[test]
+    fn solve_normalized_input() {
+        for input in ["a\r\n\r\nb\r\n", "a\n\nb\n\n", "a\n\nb"] {
+            assert_eq!(Some("\"a\\n\\nb\""), Echo.solve(input, None).unwrap().part1.as_deref());
+        }
+    }
+
+    #[test]
+    fn solve_malformed_input() {
+        let err = Sum.solve("2\nx", None).unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+        assert!(Sum.time("2\n3x").is_err());
+    }
+
+    #[test]
+    fn parse_part() {
+        assert_eq!(Ok(Part::One), "1".parse());
+        assert_eq!(Ok(Part::Two), "2".parse());
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! Local HTTP server standing in for adventofcode.com in tests
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+};
+
+/// Request received by the stub server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StubRequest {
+    /// Request line, e.g. `GET /2021/day/1/input HTTP/1.1`
+    pub line: String,
+    /// Value of the `Cookie` header (empty if missing)
+    pub cookie: String,
+    /// Request body (e.g. the submitted form)
+    pub body: String,
+}
+
+/// Starts a stub server that answers every request with the given status and body
+///
+/// Returns the base URL and a receiver for the requests.
+pub fn stub_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<StubRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut cookie = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+
+                if let Some((name, value)) = header.split_once(':') {
+                    match name.to_ascii_lowercase().as_str() {
+                        "cookie" => cookie = value.trim().to_string(),
+                        "content-length" => content_length = value.trim().parse().unwrap(),
+                        _ => {}
+                    }
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            let request = StubRequest {
+                line: line.trim().to_string(),
+                cookie,
+                body: String::from_utf8(request_body).unwrap(),
+            };
+
+            let _ = tx.send(request);
+            write!(stream, "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+        }
+    });
+
+    (url, rx)
+}
@@ -0,0 +1,409 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{find_workspace_root, AocClient, Part, USER_AGENT, YEAR};
+
+/// File in the workspace root that records every submitted answer (one JSON object per line)
+pub const SUBMISSION_LOG_FILE: &str = "submissions.jsonl";
+
+/// Verdict of the server on a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    /// Wrong, without a hint whether the answer is too high or too low
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Answer was not checked because the previous one was submitted too recently
+    Wait,
+    /// Part is already solved or not unlocked yet
+    WrongLevel,
+    /// Response could not be recognized
+    Unknown,
+}
+
+impl Outcome {
+    /// Whether the answer is known to be wrong
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wait => "submitted too recently",
+            Outcome::WrongLevel => "part already solved or locked",
+            Outcome::Unknown => "unrecognized response",
+        })
+    }
+}
+
+/// A submitted answer as recorded in the [`SubmissionLog`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: i32,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Seconds to wait before the next answer may be submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_seconds: Option<u64>,
+}
+
+/// Errors that can occur while submitting an answer
+#[derive(Debug)]
+pub enum SubmitError {
+    /// Part has already been solved with the given answer
+    Solved(String),
+    /// Answer has already been submitted and was wrong
+    Known { answer: String, outcome: Outcome },
+    /// Answer is out of the bounds given by an earlier too high/too low answer
+    Bounded { bound: String, outcome: Outcome },
+    /// Server does not accept answers yet, the duration is the remaining time to wait
+    RateLimited(Duration),
+    /// Server responded with an error status code
+    Status(u16),
+    /// Server cannot be reached or the connection failed
+    Transport(String),
+    /// Submission log cannot be read or written
+    Io(PathBuf, io::Error),
+    /// Submission log contains a line that is not a valid attempt
+    InvalidLog { path: PathBuf, line: usize, message: String },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Solved(answer) => write!(f, "part is already solved, the answer was {}", answer),
+            SubmitError::Known { answer, outcome } => write!(f, "{} has already been submitted ({})", answer, outcome),
+            SubmitError::Bounded { bound, outcome } => write!(f, "answer must be wrong, {} was {}", bound, outcome),
+            SubmitError::RateLimited(wait) => write!(f, "submitted too recently, wait {}s before trying again", wait.as_secs()),
+            SubmitError::Status(400) => write!(f, "cannot submit answer (HTTP 400), session token is invalid or expired"),
+            SubmitError::Status(status) => write!(f, "cannot submit answer (HTTP {})", status),
+            SubmitError::Transport(message) => write!(f, "cannot submit answer: {}", message),
+            SubmitError::Io(path, err) => write!(f, "cannot access submission log {}: {}", path.display(), err),
+            SubmitError::InvalidLog { path, line, message } => write!(f, "invalid submission log {}, line {}: {}", path.display(), line, message),
+        }
+    }
+}
+
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SubmitError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Recognizes the verdict in the HTML page returned for a submitted answer
+///
+/// Also returns how long to wait before the next answer may be submitted, if the page says so.
+pub fn parse_response(html: &str) -> (Outcome, Option<Duration>) {
+    let outcome = if html.contains("That's the right answer") {
+        Outcome::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if html.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if html.contains("You gave an answer too recently") {
+        Outcome::Wait
+    } else if html.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    };
+
+    (outcome, parse_wait(html))
+}
+
+/// Parses "You have 1m 5s left to wait" and "Please wait 5 minutes before trying again"
+fn parse_wait(html: &str) -> Option<Duration> {
+    if let Some(left) = html.split("You have ").nth(1).and_then(|s| s.split(" left to wait").next()) {
+        let mut seconds = 0;
+        for token in left.split_whitespace() {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let factor = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            seconds += value.parse::<u64>().ok()? * factor;
+        }
+
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let mut words = html.split("lease wait ").nth(1)?.split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        "five" => 5,
+        "ten" => 10,
+        value => value.parse().ok()?,
+    };
+    words.next().filter(|unit| unit.starts_with("minute")).map(|_| Duration::from_secs(minutes * 60))
+}
+
+/// Every answer submitted so far, stored in a JSON lines file
+///
+/// Used to refuse answers that cannot be right before they are sent to the server.
+#[derive(Debug, Clone)]
+pub struct SubmissionLog {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl SubmissionLog {
+    /// Reads the log from [`SUBMISSION_LOG_FILE`] in the workspace root
+    pub fn load() -> Result<Self, SubmitError> {
+        Self::load_from(&find_workspace_root().unwrap_or_default().join(SUBMISSION_LOG_FILE))
+    }
+
+    /// Reads the log from the given file, a missing file is an empty log
+    pub fn load_from(path: &Path) -> Result<Self, SubmitError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(SubmitError::Io(path.to_path_buf(), e)),
+        };
+
+        let attempts = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(ix, line)| {
+                serde_json::from_str(line).map_err(|e| SubmitError::InvalidLog {
+                    path: path.to_path_buf(),
+                    line: ix + 1,
+                    message: e.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SubmissionLog { path: path.to_path_buf(), attempts })
+    }
+
+    /// Recorded attempts, oldest first
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Checks whether an answer may be submitted at the given time (seconds since the Unix epoch)
+    ///
+    /// Fails if the part is already solved, the answer is known to be wrong (also if it is beyond an
+    /// answer that was too high or too low) or the server asked to wait.
+    pub fn check(&self, day: i32, part: Part, answer: &str, now: u64) -> Result<(), SubmitError> {
+        let attempts = || self.attempts.iter().filter(|a| a.day == day && a.part == part.number());
+        if let Some(solved) = attempts().find(|a| a.outcome == Outcome::Correct) {
+            return Err(SubmitError::Solved(solved.answer.clone()));
+        }
+
+        if let Some(known) = attempts().find(|a| a.answer == answer && a.outcome.is_wrong()) {
+            return Err(SubmitError::Known {
+                answer: known.answer.clone(),
+                outcome: known.outcome,
+            });
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = attempts().find(|a| match (a.outcome, a.answer.parse::<i128>()) {
+                (Outcome::TooHigh, Ok(high)) => value >= high,
+                (Outcome::TooLow, Ok(low)) => value <= low,
+                _ => false,
+            });
+            if let Some(bound) = bound {
+                return Err(SubmitError::Bounded {
+                    bound: bound.answer.clone(),
+                    outcome: bound.outcome,
+                });
+            }
+        }
+
+        // The rate limit applies to all puzzles, not only to the day of the answer
+        let wait_until = self.attempts.iter().filter_map(|a| a.wait_seconds.map(|w| a.timestamp + w)).max();
+        match wait_until {
+            Some(until) if until > now => Err(SubmitError::RateLimited(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Appends an attempt to the log file
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), SubmitError> {
+        let io_err = |e| SubmitError::Io(self.path.clone(), e);
+        let line = serde_json::to_string(&attempt).expect("attempt is always serializable");
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path).map_err(io_err)?;
+        writeln!(file, "{}", line).map_err(io_err)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+impl AocClient {
+    /// URL answers of a day are posted to
+    pub fn answer_url(&self, day: i32) -> String {
+        format!("{}/{}/day/{}/answer", self.base_url, YEAR, day)
+    }
+
+    /// Submits an answer unless the log shows that it cannot be right, and records the attempt in the log
+    pub fn submit(&self, log: &mut SubmissionLog, day: i32, part: Part, answer: &str) -> Result<Attempt, SubmitError> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        log.check(day, part, answer, now)?;
+
+        let level = part.number().to_string();
+        let response = ureq::post(&self.answer_url(day))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|e| match e {
+                ureq::Error::Status(status, _) => SubmitError::Status(status),
+                ureq::Error::Transport(t) => SubmitError::Transport(t.to_string()),
+            })?;
+        let html = response.into_string().map_err(|e| SubmitError::Transport(e.to_string()))?;
+
+        let (outcome, wait) = parse_response(&html);
+        let attempt = Attempt {
+            day,
+            part: part.number(),
+            answer: answer.to_string(),
+            outcome,
+            timestamp: now,
+            wait_seconds: wait.map(|w| w.as_secs()),
+        };
+        log.record(attempt.clone())?;
+        Ok(attempt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::stub_server;
+    use std::env;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. \
+                            Please wait one minute before trying again. <a href=\"/2021/day/1\">[Return to Day 1]</a></p></article>";
+
+    fn temp_log(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-submit-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn attempt(part: u8, answer: &str, outcome: Outcome, timestamp: u64, wait_seconds: Option<u64>) -> Attempt {
+        Attempt {
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp,
+            wait_seconds,
+        }
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!((Outcome::Correct, None), parse_response("<p>That's the right answer!  You are <span>one gold star</span> closer.</p>"));
+        assert_eq!((Outcome::TooHigh, Some(Duration::from_secs(60))), parse_response(TOO_HIGH));
+        assert_eq!(
+            (Outcome::TooLow, Some(Duration::from_secs(300))),
+            parse_response("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.")
+        );
+        assert_eq!((Outcome::Wrong, None), parse_response("That's not the right answer."));
+        assert_eq!(
+            (Outcome::Wait, Some(Duration::from_secs(83))),
+            parse_response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.")
+        );
+        assert_eq!(
+            (Outcome::WrongLevel, None),
+            parse_response("You don't seem to be solving the right level.  Did you already complete it?")
+        );
+        assert_eq!((Outcome::Unknown, None), parse_response("<html></html>"));
+    }
+
+    #[test]
+    fn refuse_known_answers() {
+        let mut log = SubmissionLog::load_from(&temp_log("check")).unwrap();
+        log.attempts = vec![
+            attempt(1, "100", Outcome::TooHigh, 1000, Some(60)),
+            attempt(1, "10", Outcome::TooLow, 1100, Some(60)),
+            attempt(1, "abc", Outcome::Wrong, 1200, None),
+            attempt(2, "7", Outcome::Correct, 1300, None),
+        ];
+
+        assert!(matches!(log.check(1, Part::One, "10", 2000), Err(SubmitError::Known { outcome: Outcome::TooLow, .. })));
+        assert!(matches!(log.check(1, Part::One, "abc", 2000), Err(SubmitError::Known { outcome: Outcome::Wrong, .. })));
+        assert!(matches!(log.check(1, Part::One, "150", 2000), Err(SubmitError::Bounded { outcome: Outcome::TooHigh, .. })));
+        assert!(matches!(log.check(1, Part::One, "-3", 2000), Err(SubmitError::Bounded { outcome: Outcome::TooLow, .. })));
+        assert!(matches!(log.check(1, Part::Two, "8", 2000), Err(SubmitError::Solved(a)) if a == "7"));
+        assert!(log.check(1, Part::One, "50", 2000).is_ok());
+        assert!(log.check(2, Part::One, "10", 2000).is_ok());
+
+        match log.check(2, Part::One, "1", 1130) {
+            Err(SubmitError::RateLimited(wait)) => assert_eq!(Duration::from_secs(30), wait),
+            other => panic!("expected rate limit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn submit_and_record() {
+        let (url, requests) = stub_server(200, TOO_HIGH);
+        let client = AocClient::new("secret").with_base_url(url);
+        let path = temp_log("submit");
+        let mut log = SubmissionLog::load_from(&path).unwrap();
+
+        let attempt = client.submit(&mut log, 1, Part::Two, "1234").unwrap();
+        assert_eq!(Outcome::TooHigh, attempt.outcome);
+        assert_eq!(Some(60), attempt.wait_seconds);
+        let request = requests.recv().unwrap();
+        assert_eq!("POST /2021/day/1/answer HTTP/1.1", request.line);
+        assert_eq!("session=secret", request.cookie);
+        assert_eq!("level=2&answer=1234", request.body);
+
+        // The attempt survives a reload and the same answer is not sent again
+        let mut log = SubmissionLog::load_from(&path).unwrap();
+        assert_eq!(vec![attempt], log.attempts);
+        assert!(matches!(client.submit(&mut log, 1, Part::Two, "1234"), Err(SubmitError::Known { .. })));
+        assert!(requests.try_recv().is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn invalid_log() {
+        let path = temp_log("invalid");
+        fs::write(&path, "{\"day\":1,\"part\":1,\"answer\":\"5\",\"outcome\":\"correct\",\"timestamp\":0}\nnot json\n").unwrap();
+        assert!(matches!(SubmissionLog::load_from(&path), Err(SubmitError::InvalidLog { line: 2, .. })));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn error_status() {
+        let (url, _requests) = stub_server(400, "");
+        let client = AocClient::new("expired").with_base_url(url);
+        let path = temp_log("status");
+        let mut log = SubmissionLog::load_from(&path).unwrap();
+        assert!(matches!(client.submit(&mut log, 1, Part::One, "1"), Err(SubmitError::Status(400))));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,441 @@
+use std::{
+    cell::RefCell,
+    env, fmt,
+    fs::File,
+    io::{self, LineWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Map, Value};
+
+/// Environment variable that enables tracing: `stderr` (or `1`) or the path of a JSON lines file
+pub const TRACE_ENV: &str = "AOC_TRACE";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+static START: OnceLock<Instant> = OnceLock::new();
+
+thread_local! {
+    /// Names of the spans the current thread is in, outermost first
+    static SPANS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Where trace records are written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceOutput {
+    /// Human readable lines, indented by span
+    Stderr,
+    /// One JSON object per line
+    JsonFile(PathBuf),
+}
+
+impl TraceOutput {
+    /// Parses the value of [`TRACE_ENV`] or `--trace=<value>`, `None` if tracing is disabled (empty, `0` or `off`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "" | "0" | "off" => None,
+            "1" | "stderr" => Some(TraceOutput::Stderr),
+            path => Some(TraceOutput::JsonFile(PathBuf::from(path))),
+        }
+    }
+}
+
+/// Removes `--trace` (stderr) and `--trace=<stderr|path>` from the command line arguments
+///
+/// Without these arguments, the output is taken from [`TRACE_ENV`].
+pub fn split_trace_args(args: Vec<String>) -> (Vec<String>, Option<TraceOutput>) {
+    let mut output = None;
+    let mut flag = false;
+    let args = args
+        .into_iter()
+        .filter(|arg| match arg.strip_prefix("--trace") {
+            Some("") => {
+                (flag, output) = (true, Some(TraceOutput::Stderr));
+                false
+            }
+            Some(value) if value.starts_with('=') => {
+                (flag, output) = (true, TraceOutput::parse(&value[1..]));
+                false
+            }
+            _ => true,
+        })
+        .collect();
+
+    if !flag {
+        output = env::var(TRACE_ENV).ok().and_then(|v| TraceOutput::parse(&v));
+    }
+
+    (args, output)
+}
+
+/// Value of a field of a span or event
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceValue {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+}
+
+macro_rules! impl_from {
+    ($variant:ident($target:ty): $($ty:ty),*) => {
+        $(impl From<$ty> for TraceValue {
+            fn from(value: $ty) -> Self {
+                TraceValue::$variant(value as $target)
+            }
+        })*
+    };
+}
+
+impl_from!(Int(i64): i8, i16, i32, i64, isize);
+impl_from!(UInt(u64): u8, u16, u32, u64, usize);
+impl_from!(Float(f64): f32, f64);
+
+impl From<bool> for TraceValue {
+    fn from(value: bool) -> Self {
+        TraceValue::Bool(value)
+    }
+}
+
+impl From<&str> for TraceValue {
+    fn from(value: &str) -> Self {
+        TraceValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for TraceValue {
+    fn from(value: String) -> Self {
+        TraceValue::Str(value)
+    }
+}
+
+impl fmt::Display for TraceValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceValue::Int(v) => write!(f, "{}", v),
+            TraceValue::UInt(v) => write!(f, "{}", v),
+            TraceValue::Float(v) => write!(f, "{}", v),
+            TraceValue::Bool(v) => write!(f, "{}", v),
+            TraceValue::Str(v) => write!(f, "{:?}", v),
+        }
+    }
+}
+
+impl From<&TraceValue> for Value {
+    fn from(value: &TraceValue) -> Self {
+        match value {
+            TraceValue::Int(v) => json!(v),
+            TraceValue::UInt(v) => json!(v),
+            TraceValue::Float(v) => json!(v),
+            TraceValue::Bool(v) => json!(v),
+            TraceValue::Str(v) => json!(v),
+        }
+    }
+}
+
+/// Kind of a trace record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+    Event,
+    /// Span was entered
+    Enter,
+    /// Span was left after the given time
+    Exit(Duration),
+}
+
+/// Entry of the trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceRecord<'a> {
+    pub kind: RecordKind,
+    pub name: &'static str,
+    /// Spans the record is in, outermost first (without the span itself for [`RecordKind::Enter`] and [`RecordKind::Exit`])
+    pub spans: &'a [&'static str],
+    pub fields: &'a [(&'static str, TraceValue)],
+    /// Time since tracing started
+    pub time: Duration,
+    /// Name of the thread, if it has one
+    pub thread: Option<&'a str>,
+}
+
+impl TraceRecord<'_> {
+    /// Human readable line as written to stderr, e.g. `    1.250ms   - scanner_aligned scanner=3 reference=0`
+    pub fn to_line(&self) -> String {
+        let marker = match self.kind {
+            RecordKind::Event => "- ",
+            RecordKind::Enter => "> ",
+            RecordKind::Exit(_) => "< ",
+        };
+
+        let mut line = format!("{:>9.3}ms ", self.time.as_secs_f64() * 1000.0);
+        if let Some(thread) = self.thread.filter(|t| *t != "main") {
+            line.push_str(&format!("[{}] ", thread));
+        }
+
+        line.push_str(&"  ".repeat(self.spans.len()));
+        line.push_str(marker);
+        line.push_str(self.name);
+        self.fields.iter().for_each(|(key, value)| line.push_str(&format!(" {}={}", key, value)));
+        if let RecordKind::Exit(elapsed) = self.kind {
+            line.push_str(&format!(" ({:.2?})", elapsed));
+        }
+
+        line
+    }
+
+    /// JSON object as written to the log file
+    pub fn to_json(&self) -> Value {
+        let kind = match self.kind {
+            RecordKind::Event => "event",
+            RecordKind::Enter => "enter",
+            RecordKind::Exit(_) => "exit",
+        };
+
+        let fields: Map<String, Value> = self.fields.iter().map(|(key, value)| (key.to_string(), value.into())).collect();
+        let mut record = json!({
+            "time_us": self.time.as_micros() as u64,
+            "thread": self.thread,
+            "kind": kind,
+            "name": self.name,
+            "spans": self.spans,
+            "fields": fields,
+        });
+
+        if let RecordKind::Exit(elapsed) = self.kind {
+            record["elapsed_us"] = json!(elapsed.as_micros() as u64);
+        }
+
+        record
+    }
+}
+
+enum Sink {
+    Stderr,
+    Json(LineWriter<File>),
+}
+
+/// Whether trace records are written, checked by the macros before the fields are evaluated
+pub fn tracing_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Starts writing trace records to the given output (replacing a previous output)
+pub fn init_tracing(output: TraceOutput) -> io::Result<()> {
+    let sink = match output {
+        TraceOutput::Stderr => Sink::Stderr,
+        TraceOutput::JsonFile(path) => Sink::Json(LineWriter::new(File::create(path)?)),
+    };
+
+    START.get_or_init(Instant::now);
+    *SINK.lock().unwrap() = Some(sink);
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Stops tracing and closes the log file
+pub fn stop_tracing() {
+    ENABLED.store(false, Ordering::Relaxed);
+    SINK.lock().unwrap().take();
+}
+
+/// Writes a record, used by the macros
+#[doc(hidden)]
+pub fn emit(kind: RecordKind, name: &'static str, fields: &[(&'static str, TraceValue)]) {
+    let mut sink = SINK.lock().unwrap();
+    let Some(sink) = sink.as_mut() else {
+        return;
+    };
+
+    let current = thread::current();
+    SPANS.with_borrow(|spans| {
+        let record = TraceRecord {
+            kind,
+            name,
+            spans,
+            fields,
+            time: START.get().map_or(Duration::ZERO, Instant::elapsed),
+            thread: current.name(),
+        };
+
+        // Tracing must never make a solution fail, write errors are ignored
+        let _ = match sink {
+            Sink::Stderr => writeln!(io::stderr(), "{}", record.to_line()),
+            Sink::Json(file) => writeln!(file, "{}", record.to_json()),
+        };
+    });
+}
+
+/// Span entered by [`trace_span!`](crate::trace_span), left when dropped
+#[must_use = "the span is left when the guard is dropped"]
+pub struct SpanGuard(Option<(&'static str, Instant)>);
+
+impl SpanGuard {
+    /// Enters a span, used by the macro
+    #[doc(hidden)]
+    pub fn enter(name: &'static str, fields: &[(&'static str, TraceValue)]) -> Self {
+        emit(RecordKind::Enter, name, fields);
+        SPANS.with_borrow_mut(|spans| spans.push(name));
+        SpanGuard(Some((name, Instant::now())))
+    }
+
+    /// Guard of a span that is not traced
+    pub fn disabled() -> Self {
+        SpanGuard(None)
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Some((name, start)) = self.0 {
+            SPANS.with_borrow_mut(|spans| spans.pop());
+            emit(RecordKind::Exit(start.elapsed()), name, &[]);
+        }
+    }
+}
+
+/// Writes a trace event if tracing is enabled: `trace_event!("split", value = n, pair = %pair, rest = ?list)`
+///
+/// Fields are converted with [`TraceValue::from`], `%` uses `Display` and `?` uses `Debug` instead. They are only
+/// evaluated if tracing is enabled.
+#[macro_export]
+macro_rules! trace_event {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        if $crate::tracing_enabled() {
+            $crate::emit($crate::RecordKind::Event, $name, &$crate::trace_fields!(@ [] $($($fields)*)?));
+        }
+    };
+}
+
+/// Enters a span if tracing is enabled, returns a guard that leaves it when dropped
+///
+/// Fields as in [`trace_event!`]: `let _span = trace_span!("align", scanner = i);`
+#[macro_export]
+macro_rules! trace_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        if $crate::tracing_enabled() {
+            $crate::SpanGuard::enter($name, &$crate::trace_fields!(@ [] $($($fields)*)?))
+        } else {
+            $crate::SpanGuard::disabled()
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! trace_fields {
+    (@ [$($acc:expr,)*]) => {
+        [$($acc,)*]
+    };
+    (@ [$($acc:expr,)*] $key:ident = %$value:expr $(, $($rest:tt)*)?) => {
+        $crate::trace_fields!(@ [$($acc,)* (stringify!($key), $crate::TraceValue::Str(($value).to_string())),] $($($rest)*)?)
+    };
+    (@ [$($acc:expr,)*] $key:ident = ?$value:expr $(, $($rest:tt)*)?) => {
+        $crate::trace_fields!(@ [$($acc,)* (stringify!($key), $crate::TraceValue::Str(format!("{:?}", $value))),] $($($rest)*)?)
+    };
+    (@ [$($acc:expr,)*] $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::trace_fields!(@ [$($acc,)* (stringify!($key), $crate::TraceValue::from($value)),] $($($rest)*)?)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_output() {
+        assert_eq!(None, TraceOutput::parse(""));
+        assert_eq!(None, TraceOutput::parse("off"));
+        assert_eq!(Some(TraceOutput::Stderr), TraceOutput::parse("1"));
+        assert_eq!(Some(TraceOutput::JsonFile(PathBuf::from("trace.jsonl"))), TraceOutput::parse("trace.jsonl"));
+
+        assert_eq!((args(&["19", "--part", "1"]), Some(TraceOutput::Stderr)), split_trace_args(args(&["19", "--trace", "--part", "1"])));
+        assert_eq!(
+            (args(&["19"]), Some(TraceOutput::JsonFile(PathBuf::from("t.jsonl")))),
+            split_trace_args(args(&["--trace=t.jsonl", "19"]))
+        );
+        assert_eq!((args(&["19"]), None), split_trace_args(args(&["19", "--trace=off"])));
+    }
+
+    #[test]
+    fn fields() {
+        let pair = (1, 2);
+        let fields = trace_fields!(@ [] depth = 4u8, offset = -3, pair = ?pair, name = %"a b", lit = true);
+        assert_eq!(
+            [
+                ("depth", TraceValue::UInt(4)),
+                ("offset", TraceValue::Int(-3)),
+                ("pair", TraceValue::Str("(1, 2)".to_string())),
+                ("name", TraceValue::Str("a b".to_string())),
+                ("lit", TraceValue::Bool(true))
+            ],
+            fields
+        );
+        let empty: [(&str, TraceValue); 0] = trace_fields!(@ []);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn format_records() {
+        let fields = [("scanner", TraceValue::UInt(3)), ("offset", TraceValue::Str("68,-1246".to_string()))];
+        let record = TraceRecord {
+            kind: RecordKind::Event,
+            name: "scanner_aligned",
+            spans: &["solve", "part1"],
+            fields: &fields,
+            time: Duration::from_micros(1250),
+            thread: Some("main"),
+        };
+        assert_eq!("    1.250ms     - scanner_aligned scanner=3 offset=\"68,-1246\"", record.to_line());
+        assert_eq!(
+            json!({"time_us": 1250, "thread": "main", "kind": "event", "name": "scanner_aligned", "spans": ["solve", "part1"], "fields": {"scanner": 3, "offset": "68,-1246"}}),
+            record.to_json()
+        );
+
+        let record = TraceRecord {
+            kind: RecordKind::Exit(Duration::from_millis(2)),
+            spans: &[],
+            fields: &[],
+            thread: Some("day19"),
+            ..record
+        };
+        assert_eq!("    1.250ms [day19] < scanner_aligned (2.00ms)", record.to_line());
+        assert_eq!(json!(2000), record.to_json()["elapsed_us"]);
+    }
+
+    #[test]
+    fn write_json_file() {
+        let path = env::temp_dir().join(format!("aoc-trace-{}.jsonl", std::process::id()));
+        init_tracing(TraceOutput::JsonFile(path.clone())).unwrap();
+        {
+            let _span = trace_span!("trace_test_span", day = 19);
+            trace_event!("trace_test_event", value = 10);
+        }
+        stop_tracing();
+        trace_event!("trace_test_event", value = 11);
+
+        // Other tests may trace concurrently, only the records of this test are checked
+        let records: Vec<Value> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str::<Value>(l).unwrap())
+            .filter(|r| r["name"].as_str().unwrap().starts_with("trace_test_"))
+            .collect();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(3, records.len());
+        assert_eq!(("enter", json!({"day": 19})), (records[0]["kind"].as_str().unwrap(), records[0]["fields"].clone()));
+        assert_eq!((json!(["trace_test_span"]), json!(10)), (records[1]["spans"].clone(), records[1]["fields"]["value"].clone()));
+        assert_eq!(("exit", json!([])), (records[2]["kind"].as_str().unwrap(), records[2]["spans"].clone()));
+    }
+}
@@ -0,0 +1,198 @@
+use std::{collections::BTreeMap, error::Error, fmt, fs, io, path::PathBuf};
+
+use serde::Deserialize;
+use termion::{color, style};
+
+use crate::{find_workspace_root, paint, print_day_header, try_read_input, Answers, DynSolution, InputError, InputSource, Part};
+
+/// Name of the file with the known answers in the workspace root
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known answers of a day
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayAnswers {
+    pub star1: Option<String>,
+    pub star2: Option<String>,
+}
+
+impl DayAnswers {
+    /// Known answer of the given part
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.star1.as_deref(),
+            Part::Two => self.star2.as_deref(),
+        }
+    }
+}
+
+/// Known answers of all days, read from [`ANSWERS_FILE`]
+///
+/// ```toml
+/// [day01]
+/// star1 = "1301"
+/// star2 = "1346"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers(BTreeMap<i32, DayAnswers>);
+
+impl ExpectedAnswers {
+    /// Parses the content of an answers file
+    pub fn parse(content: &str) -> Result<Self, AnswersError> {
+        let days: BTreeMap<String, DayAnswers> = toml::from_str(content).map_err(|e| AnswersError::Parse(Box::new(e)))?;
+        days.into_iter()
+            .map(|(key, answers)| match key.strip_prefix("day").and_then(|d| d.parse().ok()) {
+                Some(day) => Ok((day, answers)),
+                None => Err(AnswersError::InvalidDay(key)),
+            })
+            .collect::<Result<_, _>>()
+            .map(ExpectedAnswers)
+    }
+
+    /// Reads the answers file from the workspace root
+    pub fn load() -> Result<Self, AnswersError> {
+        let path = find_workspace_root().unwrap_or_default().join(ANSWERS_FILE);
+        let content = fs::read_to_string(&path).map_err(|e| AnswersError::Io(path, e))?;
+        Self::parse(&content)
+    }
+
+    /// Known answers of a day
+    pub fn day(&self, day: i32) -> Option<&DayAnswers> {
+        self.0.get(&day)
+    }
+}
+
+/// Errors that can occur while loading the known answers
+#[derive(Debug)]
+pub enum AnswersError {
+    /// Answers file cannot be read
+    Io(PathBuf, io::Error),
+    /// Answers file is not valid TOML or contains unexpected fields
+    Parse(Box<toml::de::Error>),
+    /// Section name is not of the form `dayNN`
+    InvalidDay(String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io(path, err) => write!(f, "cannot read answers file {}: {}", path.display(), err),
+            AnswersError::Parse(err) => write!(f, "invalid answers file: {}", err),
+            AnswersError::InvalidDay(key) => write!(f, "invalid section '{}' in answers file, expected dayNN", key),
+        }
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnswersError::Io(_, err) => Some(err),
+            AnswersError::Parse(err) => Some(err),
+            AnswersError::InvalidDay(_) => None,
+        }
+    }
+}
+
+/// Result of comparing an answer with the known answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Answer matches the known answer
+    Pass(String),
+    /// Answer differs from the known answer
+    Fail { expected: String, actual: String },
+    /// There is no known answer to compare with
+    Missing(String),
+}
+
+impl Verdict {
+    /// Compares an answer with the known answer (if any)
+    pub fn new(expected: Option<&str>, actual: &str) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Verdict::Pass(actual.to_string()),
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+            None => Verdict::Missing(actual.to_string()),
+        }
+    }
+
+    /// Whether the answer is known to be wrong
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Verdict::Fail { .. })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass(actual) => write!(f, "{} ({})", paint("pass", color::Fg(color::Green)), actual),
+            Verdict::Fail { expected, actual } => write!(f, "{} (expected {}, got {})", paint("FAIL", format!("{}{}", style::Bold, color::Fg(color::Red))), expected, actual),
+            Verdict::Missing(actual) => write!(f, "{} (got {})", paint("missing", color::Fg(color::Yellow)), actual),
+        }
+    }
+}
+
+/// Compares the answers of a day with the known answers
+pub fn verify_answers(expected: Option<&DayAnswers>, answers: &Answers) -> Vec<(Part, Verdict)> {
+    answers.iter().map(|(part, actual)| (part, Verdict::new(expected.and_then(|e| e.get(part)), actual))).collect()
+}
+
+/// Solves a day and prints a verdict for each star
+pub fn verify_solution(solution: &dyn DynSolution, input: InputSource, part: Option<Part>, expected: Option<&DayAnswers>) -> Result<Vec<(Part, Verdict)>, InputError> {
+    print_day_header(solution.day());
+    let content = try_read_input(input.clone())?;
+    let answers = solution.solve(&content, part).map_err(|e| InputError::Parse(input, e))?;
+    let verdicts = verify_answers(expected, &answers);
+    for (part, verdict) in verdicts.iter() {
+        println!("  Star {}: {}", part.number(), verdict);
+    }
+
+    Ok(verdicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = ExpectedAnswers::parse("[day01]\nstar1 = \"1301\"\nstar2 = \"1346\"\n\n[day25]\nstar1 = \"278\"\n").unwrap();
+        assert_eq!(Some("1346"), answers.day(1).unwrap().get(Part::Two));
+        assert_eq!(None, answers.day(25).unwrap().get(Part::Two));
+        assert_eq!(None, answers.day(2));
+    }
+
+    #[test]
+    fn parse_invalid_answers() {
+        assert!(matches!(ExpectedAnswers::parse("[first]\nstar1 = \"1\""), Err(AnswersError::InvalidDay(_))));
+        assert!(matches!(ExpectedAnswers::parse("[day01]\nstar3 = \"1\""), Err(AnswersError::Parse(_))));
+        assert!(matches!(ExpectedAnswers::parse("[day01]\nstar1 = 1"), Err(AnswersError::Parse(_))));
+    }
+
+    #[test]
+    fn verdicts() {
+        let expected = DayAnswers {
+            star1: Some("1".to_string()),
+            star2: Some("2".to_string()),
+        };
+        let answers = Answers {
+            part1: Some("1".to_string()),
+            part2: Some("3".to_string()),
+        };
+
+        let verdicts = verify_answers(Some(&expected), &answers);
+        assert_eq!(Verdict::Pass("1".to_string()), verdicts[0].1);
+        assert!(verdicts[1].1.is_fail());
+        assert_eq!(
+            vec![(Part::One, Verdict::Missing("1".to_string())), (Part::Two, Verdict::Missing("3".to_string()))],
+            verify_answers(None, &answers)
+        );
+    }
+
+    #[test]
+    fn workspace_answers_file() {
+        let answers = ExpectedAnswers::load().unwrap();
+        assert!(answers.day(1).is_some());
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use termion::{clear, color, cursor, event::Key, input::TermRead, raw::IntoRawMode, screen::AlternateScreen, style};
+
+use crate::ParseError;
+
+pub use termion::color::Rgb;
+
+/// Colors of the characters of a frame, characters without an entry use the default color of the terminal
+pub type Palette = &'static [(char, Rgb)];
+
+/// State of an animation rendered as text
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Frame {
+    /// Shown above the frame, e.g. the current step
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl Frame {
+    /// Creates a frame from the lines of a text (e.g. the `to_string` of a grid)
+    pub fn new(title: impl Into<String>, text: &str) -> Self {
+        Frame {
+            title: title.into(),
+            lines: text.lines().map(str::to_string).collect(),
+        }
+    }
+
+    /// Renders the frame without colors as written by [`dump_frames`]
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n\n", self.title);
+        self.lines.iter().for_each(|line| {
+            text.push_str(line);
+            text.push('\n');
+        });
+        text
+    }
+}
+
+/// Simulation that can be shown step by step by [`play`] or [`dump_frames`]
+pub trait Animation {
+    /// Renders the current state
+    fn frame(&self) -> Frame;
+
+    /// Advances to the next state, returns `false` if the animation already ended (the state is unchanged then)
+    fn step(&mut self) -> bool;
+
+    /// Colors of the characters used by the frames
+    fn palette(&self) -> Palette {
+        &[]
+    }
+}
+
+/// Animation of a day, used by `aoc visualize`
+#[derive(Debug, Clone, Copy)]
+pub struct Visualization {
+    /// Day of the puzzle
+    pub day: i32,
+    /// What is shown (e.g. "sea cucumbers moving until they stop")
+    pub description: &'static str,
+    /// Creates the animation for a puzzle input
+    pub create: fn(&str) -> Result<Box<dyn Animation>, ParseError>,
+}
+
+/// Renders a line with the colors of the palette, at most `width` characters
+pub fn render_line(line: &str, palette: Palette, width: usize) -> String {
+    let mut result = String::new();
+    let mut current = None;
+    for c in line.chars().take(width) {
+        let rgb = palette.iter().find(|(p, _)| *p == c).map(|(_, rgb)| *rgb);
+        if rgb != current {
+            match rgb {
+                Some(rgb) => write!(result, "{}", color::Fg(rgb)),
+                None => write!(result, "{}", color::Fg(color::Reset)),
+            }
+            .unwrap();
+            current = rgb;
+        }
+
+        result.push(c);
+    }
+
+    if current.is_some() {
+        write!(result, "{}", color::Fg(color::Reset)).unwrap();
+    }
+
+    result
+}
+
+/// What the player has to do after a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerAction {
+    /// Leave the player
+    Quit,
+    /// Show the next frame
+    Step,
+    /// State of the player changed, redraw the status line
+    Redraw,
+    /// Key has no function
+    Ignore,
+}
+
+/// Playback state of [`play`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    /// Frames per second while playing
+    pub fps: f64,
+    pub paused: bool,
+    /// Number of the shown frame (0 is the initial state)
+    pub frame: usize,
+    /// Whether the animation ended
+    pub finished: bool,
+}
+
+impl Player {
+    /// Slowest and fastest speed selectable with `-` and `+`
+    pub const FPS_RANGE: (f64, f64) = (0.25, 240.0);
+
+    /// Creates a player at the first frame
+    pub fn new(fps: f64, paused: bool) -> Self {
+        Player {
+            fps: fps.clamp(Self::FPS_RANGE.0, Self::FPS_RANGE.1),
+            paused,
+            frame: 0,
+            finished: false,
+        }
+    }
+
+    /// Handles a key: space pauses or resumes, `n` or right steps a single frame, `+`/`-` or up/down change
+    /// the speed, `q`, escape or Ctrl-C quit
+    pub fn handle_key(&mut self, key: Key) -> PlayerAction {
+        match key {
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => PlayerAction::Quit,
+            Key::Char(' ') => {
+                self.paused = !self.paused;
+                PlayerAction::Redraw
+            }
+            Key::Char('n') | Key::Right => {
+                self.paused = true;
+                PlayerAction::Step
+            }
+            Key::Char('+') | Key::Char('=') | Key::Up => self.set_fps(self.fps * 2.0),
+            Key::Char('-') | Key::Down => self.set_fps(self.fps / 2.0),
+            _ => PlayerAction::Ignore,
+        }
+    }
+
+    fn set_fps(&mut self, fps: f64) -> PlayerAction {
+        self.fps = fps.clamp(Self::FPS_RANGE.0, Self::FPS_RANGE.1);
+        PlayerAction::Redraw
+    }
+
+    /// Time between two frames while playing
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+
+    /// Advances the animation unless it already ended, returns whether a new frame has to be shown
+    pub fn advance(&mut self, animation: &mut dyn Animation) -> bool {
+        if self.finished {
+            return false;
+        }
+
+        if animation.step() {
+            self.frame += 1;
+        } else {
+            self.finished = true;
+        }
+
+        true
+    }
+
+    /// Status line shown above the frame
+    pub fn status(&self, title: &str) -> String {
+        let state = match (self.finished, self.paused) {
+            (true, _) => "finished",
+            (false, true) => "paused",
+            (false, false) => "playing",
+        };
+        format!("{} | frame {} | {} fps | {} | space: pause, n: step, +/-: speed, q: quit", title, self.frame, self.fps, state)
+    }
+}
+
+/// Plays an animation in the terminal until the user quits
+///
+/// Uses the alternate screen in raw mode, frames larger than the terminal are cropped.
+pub fn play(animation: &mut dyn Animation, fps: f64) -> io::Result<()> {
+    let mut screen = AlternateScreen::from(io::stdout().into_raw_mode()?);
+    let mut keys = termion::async_stdin().keys();
+    let mut player = Player::new(fps, false);
+    let palette = animation.palette();
+    write!(screen, "{}", cursor::Hide)?;
+
+    let mut redraw = true;
+    let mut next_frame = Instant::now() + player.interval();
+    loop {
+        if redraw {
+            let frame = animation.frame();
+            let (width, height) = termion::terminal_size()?;
+            let status = player.status(&frame.title);
+            write!(screen, "{}{}", clear::All, cursor::Goto(1, 1))?;
+            write!(screen, "{}{}{}", style::Bold, status.chars().take(width as usize).collect::<String>(), style::Reset)?;
+            for (line, row) in frame.lines.iter().zip(2..=height) {
+                write!(screen, "{}{}", cursor::Goto(1, row), render_line(line, palette, width as usize))?;
+            }
+
+            screen.flush()?;
+            redraw = false;
+        }
+
+        for key in keys.by_ref() {
+            match player.handle_key(key?) {
+                PlayerAction::Quit => {
+                    write!(screen, "{}", cursor::Show)?;
+                    return screen.flush();
+                }
+                PlayerAction::Step => redraw |= player.advance(animation),
+                PlayerAction::Redraw => redraw = true,
+                PlayerAction::Ignore => {}
+            }
+        }
+
+        if !player.paused && Instant::now() >= next_frame {
+            redraw |= player.advance(animation);
+            next_frame = Instant::now() + player.interval();
+        }
+
+        thread::sleep(Duration::from_millis(5).min(player.interval()));
+    }
+}
+
+/// Writes the frames of an animation as text files `frame_00000.txt`, `frame_00001.txt`, ... into a directory
+///
+/// Stops when the animation ends or after `max_frames` frames, returns the number of written frames.
+pub fn dump_frames(animation: &mut dyn Animation, dir: &Path, max_frames: Option<usize>) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+    let mut count = 0;
+    loop {
+        fs::write(dir.join(format!("frame_{:0>5}.txt", count)), animation.frame().to_text())?;
+        count += 1;
+        if max_frames.is_some_and(|max| count >= max) || !animation.step() {
+            return Ok(count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    struct Countdown(u32);
+
+    impl Animation for Countdown {
+        fn frame(&self) -> Frame {
+            Frame::new(format!("{} left", self.0), &"#".repeat(self.0 as usize))
+        }
+
+        fn step(&mut self) -> bool {
+            if self.0 == 0 {
+                return false;
+            }
+
+            self.0 -= 1;
+            true
+        }
+    }
+
+    #[test]
+    fn render() {
+        const PALETTE: Palette = &[('#', color::Rgb(255, 0, 0)), ('.', color::Rgb(0, 0, 255))];
+        let (red, blue, reset) = (color::Fg(color::Rgb(255, 0, 0)), color::Fg(color::Rgb(0, 0, 255)), color::Fg(color::Reset));
+
+        assert_eq!(format!("{}##{}.{} x{}#{}", red, blue, reset, red, reset), render_line("##. x#", PALETTE, 10));
+        assert_eq!(format!("{}##{}", red, reset), render_line("##. x#", PALETTE, 2));
+        assert_eq!("ab", render_line("ab", PALETTE, 10));
+        assert_eq!("2 left\n\n##\n", Countdown(2).frame().to_text());
+    }
+
+    #[test]
+    fn keys() {
+        let mut player = Player::new(10.0, false);
+        assert_eq!(PlayerAction::Redraw, player.handle_key(Key::Char(' ')));
+        assert!(player.paused);
+        assert_eq!(PlayerAction::Redraw, player.handle_key(Key::Char(' ')));
+        assert!(!player.paused);
+
+        assert_eq!(PlayerAction::Step, player.handle_key(Key::Char('n')));
+        assert!(player.paused);
+
+        player.handle_key(Key::Char('+'));
+        assert_eq!(20.0, player.fps);
+        player.handle_key(Key::Down);
+        player.handle_key(Key::Char('-'));
+        assert_eq!(Duration::from_millis(200), player.interval());
+        (0..20).for_each(|_| _ = player.handle_key(Key::Up));
+        assert_eq!(Player::FPS_RANGE.1, player.fps);
+
+        assert_eq!(PlayerAction::Ignore, player.handle_key(Key::Char('x')));
+        assert_eq!(PlayerAction::Quit, player.handle_key(Key::Esc));
+        assert_eq!(PlayerAction::Quit, player.handle_key(Key::Char('q')));
+    }
+
+    #[test]
+    fn advance() {
+        let mut animation = Countdown(2);
+        let mut player = Player::new(10.0, false);
+        assert!(player.advance(&mut animation) && player.advance(&mut animation));
+        assert_eq!((2, false), (player.frame, player.finished));
+        assert!(player.status("x").contains("playing"));
+
+        // The first step after the end marks the animation as finished, later steps do nothing
+        assert!(player.advance(&mut animation));
+        assert!(!player.advance(&mut animation));
+        assert_eq!((2, true), (player.frame, player.finished));
+        assert!(player.status("x").starts_with("x | frame 2 | 10 fps | finished"));
+    }
+
+    #[test]
+    fn dump() {
+        let dir = env::temp_dir().join(format!("aoc-visualize-{}", std::process::id()));
+        assert_eq!(4, dump_frames(&mut Countdown(3), &dir, None).unwrap());
+        assert_eq!("3 left\n\n###\n", fs::read_to_string(dir.join("frame_00000.txt")).unwrap());
+        assert_eq!("0 left\n\n", fs::read_to_string(dir.join("frame_00003.txt")).unwrap());
+        assert!(!dir.join("frame_00004.txt").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(2, dump_frames(&mut Countdown(3), &dir, Some(2)).unwrap());
+        assert!(!dir.join("frame_00002.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
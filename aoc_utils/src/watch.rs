@@ -0,0 +1,231 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use termion::{color, style};
+
+use crate::{get_input_file_name, paint, Answers, DayAnswers, Part, Verdict, NO_COLOR_ENV};
+
+/// How often [`Watcher::wait`] checks the files for changes
+pub const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Paths watched for a day: the `src` directory of its crate and its input file
+pub fn watched_paths(root: &Path, day: i32) -> Vec<PathBuf> {
+    vec![root.join(format!("day{:0>2}", day)).join("src"), get_input_file_name(day)]
+}
+
+/// Detects changes of files by polling their modification time and size
+///
+/// Directories are watched recursively, paths that do not exist (yet) are watched for being created.
+#[derive(Debug)]
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    files: BTreeMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl Watcher {
+    /// Starts watching the given files and directories
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let files = snapshot(&paths);
+        Watcher { paths, files }
+    }
+
+    /// Whether a file was created, modified or removed since the last call (or since the watcher was created)
+    pub fn changed(&mut self) -> bool {
+        let files = snapshot(&self.paths);
+        let changed = files != self.files;
+        self.files = files;
+        changed
+    }
+
+    /// Blocks until a file changes
+    pub fn wait(&mut self, interval: Duration) {
+        while !self.changed() {
+            thread::sleep(interval);
+        }
+
+        // Editors and `aoc fetch` often write in several steps, wait until the files are stable
+        loop {
+            thread::sleep(interval);
+            if !self.changed() {
+                break;
+            }
+        }
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, (SystemTime, u64)> {
+    fn visit(path: &Path, files: &mut BTreeMap<PathBuf, (SystemTime, u64)>) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+                visit(&entry.path(), files);
+            }
+        } else {
+            files.insert(path.to_path_buf(), (metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len()));
+        }
+    }
+
+    let mut files = BTreeMap::new();
+    paths.iter().for_each(|path| visit(path, &mut files));
+    files
+}
+
+/// Errors that can occur while rebuilding and running a day
+#[derive(Debug)]
+pub enum WatchError {
+    /// Cargo cannot be started
+    Spawn(io::Error),
+    /// Build failed or the binary exited with an error (the details are written to stderr)
+    Failed(ExitStatus),
+}
+
+impl fmt::Display for WatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchError::Spawn(err) => write!(f, "cannot run cargo: {}", err),
+            WatchError::Failed(status) => write!(f, "build or run failed ({})", status),
+        }
+    }
+}
+
+impl Error for WatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WatchError::Spawn(err) => Some(err),
+            WatchError::Failed(_) => None,
+        }
+    }
+}
+
+/// Rebuilds the binary of a day with `cargo run --release` in the workspace root and returns its answers
+///
+/// Compiler errors and the errors of the binary are passed through to stderr.
+pub fn run_day_binary(root: &Path, day: i32) -> Result<Answers, WatchError> {
+    let output = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .args(["run", "--quiet", "--release", "-p", &format!("day{:0>2}", day)])
+        .current_dir(root)
+        .env(NO_COLOR_ENV, "1")
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(WatchError::Spawn)?;
+
+    if !output.status.success() {
+        return Err(WatchError::Failed(output.status));
+    }
+
+    Ok(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Extracts the answers from the plain output of a day binary
+pub fn parse_answers(output: &str) -> Answers {
+    let mut answers = Answers::default();
+    for line in output.lines() {
+        match line.trim().strip_prefix("Result Star ").and_then(|l| l.split_once(": ")) {
+            Some(("1", answer)) => answers.part1 = Some(answer.to_string()),
+            Some(("2", answer)) => answers.part2 = Some(answer.to_string()),
+            _ => {}
+        }
+    }
+
+    answers
+}
+
+/// Writes a line per star with the verdict against the known answer and whether the answer changed since the previous run
+pub fn write_changes<W: Write>(out: &mut W, previous: Option<&Answers>, answers: &Answers, expected: Option<&DayAnswers>) -> io::Result<()> {
+    let previous_answer = |part| previous.and_then(|p| p.iter().find(|(p, _)| *p == part).map(|(_, a)| a));
+    for (part, answer) in answers.iter() {
+        let verdict = Verdict::new(expected.and_then(|e| e.get(part)), answer);
+        match previous_answer(part) {
+            Some(previous) if previous == answer => writeln!(out, "  Star {}: {}, unchanged", part.number(), verdict)?,
+            Some(previous) => writeln!(
+                out,
+                "  Star {}: {}, {} from {}",
+                part.number(),
+                verdict,
+                paint("changed", format!("{}{}", style::Bold, color::Fg(color::Yellow))),
+                previous
+            )?,
+            None => writeln!(out, "  Star {}: {}", part.number(), verdict)?,
+        }
+    }
+
+    if let Some(previous) = previous_answer(Part::Two).filter(|_| answers.part2.is_none()) {
+        writeln!(out, "  Star 2: no answer, was {}", previous)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(part1: &str, part2: Option<&str>) -> Answers {
+        Answers {
+            part1: Some(part1.to_string()),
+            part2: part2.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn detect_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src").join("lib.rs"), "fn main() {}").unwrap();
+        let input = dir.join("input.txt");
+
+        let mut watcher = Watcher::new(vec![dir.join("src"), input.clone()]);
+        assert!(!watcher.changed());
+        fs::write(&input, "1\n2\n").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+        fs::write(dir.join("src").join("lib.rs"), "fn main() { }").unwrap();
+        assert!(watcher.changed());
+        fs::remove_file(&input).unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_output() {
+        assert_eq!(answers("1301", Some("1346")), parse_answers("Day 1:\n  Result Star 1: 1301\n  Result Star 2: 1346\n"));
+        assert_eq!(Answers::default(), parse_answers("Day 1:\n"));
+    }
+
+    #[test]
+    fn changes() {
+        let expected = DayAnswers {
+            star1: Some("1301".to_string()),
+            star2: Some("1346".to_string()),
+        };
+
+        crate::set_color_enabled(false);
+        let write = |previous: Option<&Answers>, answers: &Answers| {
+            let mut out = Vec::new();
+            write_changes(&mut out, previous, answers, Some(&expected)).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let first = answers("1301", Some("1345"));
+        assert_eq!("  Star 1: pass (1301)\n  Star 2: FAIL (expected 1346, got 1345)\n", write(None, &first));
+        assert_eq!(
+            "  Star 1: pass (1301), unchanged\n  Star 2: pass (1346), changed from 1345\n",
+            write(Some(&first), &answers("1301", Some("1346")))
+        );
+        assert_eq!("  Star 1: pass (1301), unchanged\n  Star 2: no answer, was 1345\n", write(Some(&first), &answers("1301", None)));
+    }
+}
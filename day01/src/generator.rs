@@ -0,0 +1,41 @@
+//! Random inputs for day 1
+
+use aoc_utils::{InputGenerator, Rng};
+
+/// Generates sonar sweep reports, `size` is the number of depths
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 1,
+    size: "number of depths",
+    default_size: 2000,
+    generate,
+};
+
+/// Generates `size` depths: a random walk that mostly goes down, like the sea floor in the puzzle
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+    let mut lines = Vec::with_capacity(size);
+    for _ in 0..size {
+        lines.push(depth.to_string());
+        depth = (depth + rng.range(-10..=15)).max(0);
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+    use crate::Day01;
+    use aoc_utils::{read_lines, Solution, StreamingSolution};
+
+    #[test]
+    fn test_generate() {
+        let input = GENERATOR.generate(1, Some(500));
+        assert_eq!(input, GENERATOR.generate(1, Some(500)));
+        let depths = Day01::parse(&input).unwrap();
+        assert_eq!(500, depths.len());
+
+        let streamed = Day01::solve_lines(read_lines(input.as_bytes()).map(Result::unwrap)).unwrap();
+        assert_eq!((Day01::part1(&depths), Day01::part2(&depths)), streamed);
+    }
+}
@@ -0,0 +1,144 @@
+//! Day 1: Sonar Sweep
+#![warn(missing_docs)]
+
+use aoc_utils::{parse_lines, LineParser, ParseError, Solution, StreamingSolution};
+
+pub mod generator;
+
+/// Reads one depth per line, empty lines are skipped
+pub fn get_depths(contents: &str) -> Result<Vec<i32>, ParseError> {
+    parse_lines(contents, |line| {
+        line.skip_whitespace();
+        line.number()
+    })
+}
+
+/// Counts the depths that are larger than the previous depth
+pub fn count_increases(depths: &[i32]) -> i32 {
+    depths
+        .iter()
+        .enumerate()
+        .map(|d| {
+            if d.0 > 0 && *d.1 > depths[d.0 - 1] {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Sums of all three-measurement sliding windows (none if there are fewer than three depths)
+pub fn get_windows(depths: &[i32]) -> Vec<i32> {
+    depths.windows(3).map(|w| w.iter().sum()).collect()
+}
+
+/// Solution for day 1
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: i32 = 1;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_depths(input)
+    }
+
+    fn part1(depths: &Self::Input) -> Self::Answer1 {
+        count_increases(depths)
+    }
+
+    fn part2(depths: &Self::Input) -> Self::Answer2 {
+        count_increases(&get_windows(depths))
+    }
+}
+
+impl StreamingSolution for Day01 {
+    fn solve_lines(lines: impl Iterator<Item = (usize, String)>) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        // A window sum is larger than the previous one if the depth entering the window is larger than the one leaving it
+        let mut last = [None; 3];
+        let (mut increases, mut window_increases) = (0, 0);
+        for (number, line) in lines {
+            let mut line = LineParser::new(number, &line);
+            let depth: i32 = line.number()?;
+            line.end()?;
+
+            if last[2].is_some_and(|l| depth > l) {
+                increases += 1;
+            }
+
+            if last[0].is_some_and(|l| depth > l) {
+                window_increases += 1;
+            }
+
+            last = [last[1], last[2], Some(depth)];
+        }
+
+        Ok((increases, window_increases))
+    }
+}
+
+aoc_utils::example_tests!(Day01);
+
+#[cfg(test)]
+const TEST_INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let d = get_depths(TEST_INPUT).unwrap();
+        assert_eq!(count_increases(&d), 7);
+    }
+
+    #[test]
+    fn test_line_endings() {
+        for input in aoc_utils::line_ending_variants(TEST_INPUT) {
+            let d = get_depths(&input).unwrap();
+            assert_eq!(10, d.len());
+            assert_eq!(count_increases(&d), 7);
+        }
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!("line 2, column 1: expected number, found 'x'", get_depths("199\nx\n208").unwrap_err().to_string());
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+    use aoc_utils::{Answers, DynSolution};
+
+    #[test]
+    fn test_2() {
+        let d = get_depths(TEST_INPUT).unwrap();
+        let w = get_windows(&d);
+        assert_eq!(count_increases(&w), 5);
+    }
+
+    #[test]
+    fn test_short_input() {
+        assert!(get_windows(&[199, 200]).is_empty());
+        assert_eq!(
+            Answers {
+                part1: Some("0".to_string()),
+                part2: Some("0".to_string())
+            },
+            Day01.solve("199", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_streaming() {
+        let lines = aoc_utils::read_lines(TEST_INPUT.as_bytes()).map(Result::unwrap);
+        assert_eq!((7, 5), Day01::solve_lines(lines).unwrap());
+    }
+}
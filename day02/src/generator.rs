@@ -0,0 +1,53 @@
+//! Random inputs for day 2
+
+use aoc_utils::{InputGenerator, Rng};
+
+/// Generates planned courses, `size` is the number of commands
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 2,
+    size: "number of commands",
+    default_size: 1000,
+    generate,
+};
+
+/// Generates `size` commands with distances from 1 to 9; `up` never moves the submarine above the surface
+///
+/// The answers grow quadratically with the size, so they overflow `i32` for sizes much larger than the real input.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+    let mut lines = Vec::with_capacity(size);
+    for _ in 0..size {
+        let distance = rng.range(1..=9);
+        let direction = match rng.below(10) {
+            0..=4 => "forward",
+            5 | 6 if depth >= distance => "up",
+            _ => "down",
+        };
+
+        match direction {
+            "up" => depth -= distance,
+            "down" => depth += distance,
+            _ => {}
+        }
+
+        lines.push(format!("{} {}", direction, distance));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+    use crate::Day02;
+    use aoc_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        let input = GENERATOR.generate(2, None);
+        let movements = Day02::parse(&input).unwrap();
+        assert_eq!(1000, movements.len());
+        assert!(Day02::part1(&movements) >= 0);
+        assert!(Day02::part2(&movements) >= 0);
+    }
+}
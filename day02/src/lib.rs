@@ -0,0 +1,161 @@
+//! Day 2: Dive!
+#![warn(missing_docs)]
+
+use std::ops::AddAssign;
+
+use aoc_utils::{parse_lines, LineParser, ParseError, Solution};
+
+pub mod generator;
+
+/// Direction of a movement of the submarine
+#[derive(Clone, Copy)]
+pub enum Direction {
+    /// Increases the horizontal position
+    Forward,
+    /// Increases the depth (or the aim)
+    Down,
+    /// Decreases the depth (or the aim)
+    Up,
+}
+
+/// A command of the planned course
+#[derive(Clone, Copy)]
+pub struct Movement {
+    /// Direction of the movement
+    pub direction: Direction,
+    /// Number of units to move
+    pub distance: i32,
+}
+
+impl Movement {
+    /// Parses a line like `forward 5`
+    pub fn parse(line: &mut LineParser) -> Result<Self, ParseError> {
+        let direction = line.one_of(&[("forward", Direction::Forward), ("up", Direction::Up), ("down", Direction::Down)])?;
+        line.literal(" ")?;
+        Ok(Self { direction, distance: line.number()? })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Position {
+    dx: i32,
+    dy: i32,
+}
+
+impl Position {
+    fn new(dx: i32, dy: i32) -> Self {
+        Self { dx, dy }
+    }
+
+    fn from_movement(movement: &Movement) -> Self {
+        Self {
+            dx: match movement.direction {
+                Direction::Forward => movement.distance,
+                _ => 0,
+            },
+            dy: match movement.direction {
+                Direction::Down => movement.distance,
+                Direction::Up => -movement.distance,
+                _ => 0,
+            },
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl AddAssign for Position {
+    fn add_assign(&mut self, rhs: Self) {
+        self.dx += rhs.dx;
+        self.dy += rhs.dy;
+    }
+}
+
+/// Parses the planned course, one movement per line
+pub fn get_movements(contents: &str) -> Result<Vec<Movement>, ParseError> {
+    parse_lines(contents, Movement::parse)
+}
+
+/// Multiplies the final horizontal position and depth (star 1)
+pub fn get_position(movements: &[Movement]) -> i32 {
+    let mut result: Position = Default::default();
+    movements
+        .iter()
+        .map(Position::from_movement)
+        .for_each(|m| result += m);
+    result.dx * result.dy
+}
+
+/// Multiplies the final horizontal position and depth when down and up change the aim (star 2)
+pub fn get_position_with_aim(movements: &[Movement]) -> i32 {
+    let mut result: Position = Default::default();
+    let mut aim = 0;
+    movements.iter().for_each(|m| match m.direction {
+        Direction::Down => aim += m.distance,
+        Direction::Up => aim -= m.distance,
+        Direction::Forward => result += Position::new(m.distance, aim * m.distance),
+    });
+    result.dx * result.dy
+}
+
+/// Solution for day 2
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: i32 = 2;
+    type Input = Vec<Movement>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_movements(input)
+    }
+
+    fn part1(movements: &Self::Input) -> Self::Answer1 {
+        get_position(movements)
+    }
+
+    fn part2(movements: &Self::Input) -> Self::Answer2 {
+        get_position_with_aim(movements)
+    }
+}
+
+aoc_utils::example_tests!(Day02);
+
+#[cfg(test)]
+const TEST_INPUT: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2";
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let movements = get_movements(TEST_INPUT).unwrap();
+        assert_eq!(150, get_position(&movements));
+    }
+
+    #[test]
+    fn test_malformed_line() {
+        let err = get_movements("forward 5\nforward x").err().unwrap();
+        assert_eq!("line 2, column 9: expected number, found 'x'", err.to_string());
+        assert!(get_movements("backward 5").is_err());
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_2() {
+        let movements = get_movements(TEST_INPUT).unwrap();
+        assert_eq!(900, get_position_with_aim(&movements));
+    }
+}
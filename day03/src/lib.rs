@@ -0,0 +1,294 @@
+//! Day 3: Binary Diagnostic
+#![warn(missing_docs)]
+
+use aoc_utils::{parse_lines, LineParser, ParseError, Solution, StreamingSolution};
+use num::PrimInt;
+
+/// Reads one binary number per line, returns the numbers and the number of bits (length of the first line)
+///
+/// All numbers need the same number of digits, and they have to fit into `T`.
+pub fn get_numbers<T: PrimInt>(contents: &'_ str) -> Result<(Vec<T>, u32), ParseError> {
+    // Number of bits (=length of first line)
+    let mut bits = None;
+    let result = parse_lines(contents, |line| {
+        line.skip_whitespace();
+        let digits = line.take_while("binary number", |c| c == '0' || c == '1')?;
+        line.end()?;
+        let bits = *bits.get_or_insert(digits.len().min(T::zero().count_zeros() as usize));
+        if digits.len() != bits {
+            return Err(ParseError::new(line.line(), 1, format!("expected a binary number with {} digits", bits)));
+        }
+
+        T::from_str_radix(digits, 2).map_err(|_| ParseError::new(line.line(), 1, "invalid binary number"))
+    })?;
+
+    match bits {
+        Some(bits) => Ok((result, bits as u32)),
+        None => Err(ParseError::new(1, 1, "input is empty")),
+    }
+}
+
+/// Most common value of a bit position
+pub enum BitResult {
+    /// As many ones as zeroes
+    Equal,
+    /// Ones are more common
+    MoreOnes,
+    /// Zeroes are more common
+    MoreZeroes,
+}
+
+/// Finds the most common value of the given bit (0 = least significant bit)
+pub fn find_majority_bit<T: PrimInt + Clone>(numbers: &[T], bit: u32) -> BitResult {
+    let mut ones: usize = 0;
+    for n in numbers.iter().cloned().enumerate() {
+        if n.1.unsigned_shr(bit) & T::from(1).unwrap() == T::from(1).unwrap() {
+            ones += 1;
+        }
+    }
+
+    if (ones as f32 - numbers.len() as f32 / 2f32).abs() < 0.01 {
+        BitResult::Equal
+    } else if ones > numbers.len() / 2 {
+        BitResult::MoreOnes
+    } else {
+        BitResult::MoreZeroes
+    }
+}
+
+/// Bit criteria of the oxygen generator rating: the value of the bit to keep
+pub fn oxygen_filter<T: PrimInt>(numbers: &[T], bit: u32) -> T {
+    match find_majority_bit(numbers, bit) {
+        BitResult::Equal => T::from(1).unwrap(),
+        BitResult::MoreOnes => T::from(1).unwrap(),
+        BitResult::MoreZeroes => T::from(0).unwrap(),
+    }
+}
+
+/// Bit criteria of the CO2 scrubber rating: the value of the bit to keep
+pub fn co2_filter<T: PrimInt>(numbers: &[T], bit: u32) -> T {
+    match find_majority_bit(numbers, bit) {
+        BitResult::Equal => T::from(0).unwrap(),
+        BitResult::MoreOnes => T::from(0).unwrap(),
+        BitResult::MoreZeroes => T::from(1).unwrap(),
+    }
+}
+
+/// Calculates the gamma and epsilon rates
+pub fn aggregate<T: PrimInt>(numbers: &[T], bits: u32) -> (T, T) {
+    let mut gamma: T = T::from(0).unwrap();
+    for i in (0..bits).rev() {
+        if let BitResult::MoreOnes = find_majority_bit(numbers, i) {
+            gamma = gamma | T::from(1 << i).unwrap();
+        }
+    }
+
+    (gamma, !gamma & T::from((1 << bits) - 1).unwrap())
+}
+
+/// Filters the numbers bit by bit with the given bit criteria until one number is left
+pub fn filter<T: PrimInt>(mut numbers: Vec<T>, bits: u32, f: fn(&[T], u32) -> T) -> T {
+    for i in (0..bits).rev() {
+        let r = f(&numbers, i);
+        // Duplicates may leave several equal numbers, which must not all be removed
+        if numbers.iter().any(|n| ((*n).unsigned_shr(i)) & T::from(1).unwrap() == r) {
+            numbers.retain(|n| ((*n).unsigned_shr(i)) & T::from(1).unwrap() == r);
+        }
+        if numbers.len() == 1 {
+            break;
+        }
+    }
+
+    numbers[0]
+}
+
+/// Bit statistics of a stream of numbers, enough to calculate both stars without keeping the numbers
+///
+/// Counts how many numbers start with each prefix, stored like a binary trie in an array: the counters for
+/// prefixes of length `len` start at index `1 << len`.
+pub struct BitCounts {
+    bits: u32,
+    prefixes: Vec<usize>,
+}
+
+impl BitCounts {
+    /// Creates empty statistics for numbers with the given number of bits
+    pub fn new(bits: u32) -> Self {
+        BitCounts { bits, prefixes: vec![0; 2 << bits] }
+    }
+
+    /// Adds a number
+    pub fn add(&mut self, number: u32) {
+        for len in 0..=self.bits {
+            self.prefixes[(1 << len) + (number >> (self.bits - len)) as usize] += 1;
+        }
+    }
+
+    fn count(&self, len: u32, prefix: u32) -> usize {
+        self.prefixes[(1 << len) + prefix as usize]
+    }
+
+    /// Calculates the gamma and epsilon rates (like [`aggregate`])
+    pub fn rates(&self) -> (u32, u32) {
+        let mut gamma = 0;
+        for i in 0..self.bits {
+            // Numbers with a one at bit i are those whose prefix of length bits - i ends with a one
+            let len = self.bits - i;
+            let ones: usize = (1..1 << len).step_by(2).map(|prefix| self.count(len, prefix)).sum();
+            if ones > self.count(0, 0) / 2 {
+                gamma |= 1 << i;
+            }
+        }
+
+        (gamma, !gamma & ((1 << self.bits) - 1))
+    }
+
+    /// Finds the rating with the given bit criteria (like [`filter`]), `most_common` selects the oxygen
+    /// generator rating, otherwise the CO2 scrubber rating
+    pub fn rating(&self, most_common: bool) -> u32 {
+        let mut prefix = 0;
+        for len in 1..=self.bits {
+            let (zeroes, ones) = (self.count(len, prefix << 1), self.count(len, prefix << 1 | 1));
+            let bit = match (zeroes, ones) {
+                (0, _) => 1,
+                (_, 0) => 0,
+                _ if most_common => (ones >= zeroes) as u32,
+                _ => (ones < zeroes) as u32,
+            };
+            prefix = prefix << 1 | bit;
+        }
+
+        prefix
+    }
+}
+
+/// Solution for day 3
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: i32 = 3;
+    type Input = (Vec<u16>, u32);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_numbers::<u16>(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        let (numbers, bits) = input;
+        let result = aggregate(numbers, *bits);
+        result.0 as u32 * result.1 as u32
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        let (numbers, bits) = input;
+        let oxygen = filter(numbers.clone(), *bits, oxygen_filter);
+        let co2 = filter(numbers.clone(), *bits, co2_filter);
+        oxygen as u32 * co2 as u32
+    }
+}
+
+impl StreamingSolution for Day03 {
+    fn solve_lines(lines: impl Iterator<Item = (usize, String)>) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        let mut counts: Option<BitCounts> = None;
+        for (number, line) in lines {
+            let mut line = LineParser::new(number, &line);
+            let digits = line.take_while("binary number", |c| c == '0' || c == '1')?;
+            line.end()?;
+
+            // Like the other days, the numbers have to fit into the u16 of the parsed input
+            let counts = counts.get_or_insert_with(|| BitCounts::new(digits.len().min(16) as u32));
+            if digits.len() != counts.bits as usize {
+                return Err(ParseError::new(number, 1, format!("expected a binary number with {} digits", counts.bits)));
+            }
+
+            counts.add(u32::from_str_radix(digits, 2).unwrap());
+        }
+
+        let counts = counts.unwrap_or_else(|| BitCounts::new(0));
+        let (gamma, epsilon) = counts.rates();
+        Ok((gamma * epsilon, counts.rating(true) * counts.rating(false)))
+    }
+}
+
+aoc_utils::example_tests!(Day03);
+
+#[cfg(test)]
+const TEST_INPUT: &str =
+    "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010";
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let (numbers, bits) = get_numbers::<u16>(TEST_INPUT).unwrap();
+        let result = aggregate(&numbers, bits);
+        assert_eq!((0b10110, 0b01001), result);
+        assert_eq!(198, result.0 as u32 * result.1 as u32);
+    }
+
+    #[test]
+    fn test_line_endings() {
+        for input in aoc_utils::line_ending_variants(TEST_INPUT) {
+            let (numbers, bits) = get_numbers::<u16>(&input).unwrap();
+            assert_eq!((12, 5), (numbers.len(), bits));
+            assert_eq!(0b00100, numbers[0]);
+        }
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let err = get_numbers::<u16>("00100\n11201\n").unwrap_err();
+        assert_eq!("line 2, column 3: expected end of line, found '201'", err.to_string());
+        let err = get_numbers::<u16>("00100\n1110\n").unwrap_err();
+        assert_eq!("line 2, column 1: expected a binary number with 5 digits", err.to_string());
+        let err = get_numbers::<u16>("10000000000000000\n").unwrap_err();
+        assert_eq!("line 1, column 1: expected a binary number with 16 digits", err.to_string());
+        assert_eq!("line 1, column 1: input is empty", get_numbers::<u16>("\n").unwrap_err().to_string());
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_oxygen() {
+        let (numbers, bits) = get_numbers::<u16>(TEST_INPUT).unwrap();
+        let result = filter(numbers, bits, oxygen_filter);
+        assert_eq!(0b10111, result);
+    }
+
+    #[test]
+    fn test_co2() {
+        let (numbers, bits) = get_numbers::<u16>(TEST_INPUT).unwrap();
+        let result = filter(numbers, bits, co2_filter);
+        assert_eq!(0b01010, result);
+    }
+
+    #[test]
+    fn test_result() {
+        let (numbers, bits) = get_numbers::<u16>(TEST_INPUT).unwrap();
+        let oxygen = filter(numbers.clone(), bits, oxygen_filter);
+        let co2 = filter(numbers, bits, co2_filter);
+        assert_eq!(230, oxygen as i32 * co2 as i32);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let (numbers, bits) = get_numbers::<u16>("101\n101").unwrap();
+        assert_eq!(0b101, filter(numbers.clone(), bits, oxygen_filter));
+        assert_eq!(0b101, filter(numbers, bits, co2_filter));
+    }
+
+    #[test]
+    fn test_streaming() {
+        let lines = aoc_utils::read_lines(TEST_INPUT.as_bytes()).map(Result::unwrap);
+        assert_eq!((198, 230), Day03::solve_lines(lines).unwrap());
+    }
+}
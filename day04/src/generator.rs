@@ -0,0 +1,50 @@
+//! Random inputs for day 4
+
+use aoc_utils::{InputGenerator, Rng};
+
+/// Generates bingo subsystems, `size` is the number of cards
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 4,
+    size: "number of cards",
+    default_size: 100,
+    generate,
+};
+
+/// Generates the numbers 0 to 99 in random order followed by `size` cards of 25 distinct numbers
+///
+/// Every number is drawn, so every card wins eventually.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<u16> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let mut blocks = vec![numbers.iter().map(u16::to_string).collect::<Vec<_>>().join(",")];
+
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        let rows: Vec<String> = numbers[..25].chunks(5).map(|row| row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ")).collect();
+        blocks.push(rows.join("\n"));
+    }
+
+    blocks.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+    use crate::BingoInput;
+
+    #[test]
+    fn test_generate() {
+        let input = BingoInput::from_input(&GENERATOR.generate(4, Some(20))).unwrap();
+        assert_eq!(100, input.drawn_numbers.len());
+        assert_eq!(20, input.cards.len());
+        for card in input.cards.iter() {
+            let mut numbers: Vec<_> = card.iter().collect();
+            numbers.sort();
+            numbers.dedup();
+            assert_eq!(25, numbers.len());
+        }
+
+        assert!(input.draw().is_some());
+        assert!(input.draw_to_last_winner().is_some());
+    }
+}
@@ -0,0 +1,266 @@
+//! Day 4: Giant Squid
+#![warn(missing_docs)]
+
+use aoc_utils::{input_lines, Grid, LineParser, ParseError, Solution};
+
+pub mod generator;
+
+/// Drawn numbers and bingo cards
+#[derive(Clone)]
+pub struct BingoInput {
+    /// Numbers in the order they are drawn
+    pub drawn_numbers: Vec<u16>,
+    /// 5x5 bingo cards
+    pub cards: Vec<Grid<u16>>,
+}
+
+impl BingoInput {
+    /// Parses input
+    ///
+    /// Fails if a card can't win with the drawn numbers, so playing bingo on parsed input always finds a winner.
+    pub fn from_input(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input_lines(input);
+        let mut first = lines.next().ok_or_else(|| ParseError::new(1, 1, "input is empty"))?;
+        let drawn_numbers: Vec<u16> = first.separated(",", LineParser::number)?;
+        first.end()?;
+
+        let card = |line: usize, values: Vec<u16>| {
+            if values.len() != 25 {
+                return Err(ParseError::new(line, 1, format!("expected five rows, found {}", values.len() / 5)));
+            }
+
+            let card = Grid::new(5, 5, values);
+            if !check_win(&card.map(|v| drawn_numbers.contains(v))) {
+                return Err(ParseError::new(line, 1, "card never wins with the drawn numbers"));
+            }
+
+            Ok(card)
+        };
+
+        // Cards are separated by empty lines, i.e. gaps in the line numbers
+        let mut cards = Vec::new();
+        let mut values = Vec::with_capacity(25);
+        let (mut card_line, mut last_line) = (0, first.line());
+        for mut line in lines {
+            if line.line() > last_line + 1 && !values.is_empty() {
+                cards.push(card(card_line, std::mem::take(&mut values))?);
+            }
+            if values.is_empty() {
+                card_line = line.line();
+            }
+            last_line = line.line();
+
+            // Numbers are right-aligned with a leading space for single digits -> separated by any whitespace
+            let row_start = values.len();
+            while !line.rest().trim().is_empty() {
+                line.skip_whitespace();
+                values.push(line.number()?);
+            }
+            if values.len() - row_start != 5 {
+                return Err(ParseError::new(line.line(), 1, format!("expected five numbers, found {}", values.len() - row_start)));
+            }
+        }
+        if !values.is_empty() {
+            cards.push(card(card_line, values)?);
+        }
+
+        Ok(BingoInput{drawn_numbers, cards})
+    }
+
+    /// Get sum of undrawn numbers
+    fn sum_of_undrawn(&self, card_ix: usize, card: &Grid<bool>) -> u16 {
+        self.cards[card_ix].iter().zip(card.iter()).filter(|(_, drawn)| !**drawn).map(|(val, _)| val).sum()
+    }
+
+    fn get_drawn_grids(&self) -> Vec<Grid<bool>> {
+        vec![Grid::filled(5, 5, false); self.cards.len()]
+    }
+
+    /// Plays bingo, returns the score of the first winning card (`None` if no card wins)
+    pub fn draw(&self) -> Option<u16> {
+        let mut drawn = self.get_drawn_grids();
+        for v in self.drawn_numbers.iter().cloned() {
+            for c in self.cards.iter().enumerate() {
+                if let Some(p) = c.1.position(|val| *val == v) {
+                    drawn[c.0][p] = true;
+                }
+
+                if check_win(&drawn[c.0]) {
+                    return Some(self.sum_of_undrawn(c.0, &drawn[c.0]) * v);
+                }
+            }
+        }
+
+        None
+    }
+    
+    /// Plays bingo until all cards have won, returns the score of the last winning card (`None` if a card never wins)
+    pub fn draw_to_last_winner(mut self) -> Option<u16> {
+        let mut drawn = self.get_drawn_grids();
+
+        for v in self.drawn_numbers.iter().cloned() {
+            let mut i = 0;
+            while i < self.cards.len() {
+                if let Some(p) = self.cards[i].position(|val| *val == v) {
+                    drawn[i][p] = true;
+                }
+
+                if check_win(&drawn[i]) {
+                    if self.cards.len() == 1 {
+                        return Some(self.sum_of_undrawn(i, &drawn[i]) * v);
+                    }
+
+                    self.cards.remove(i);
+                    drawn.remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Checks whether a row or column of a card is completely drawn
+pub fn check_win(card: &Grid<bool>) -> bool {
+    card.rows().any(|row| row.iter().all(|drawn| *drawn)) || card.columns().any(|mut col| col.all(|drawn| *drawn))
+}
+
+/// Solution for day 4
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: i32 = 4;
+    type Input = BingoInput;
+    type Answer1 = u16;
+    type Answer2 = u16;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        BingoInput::from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        input.draw().expect("every card wins, checked when parsing")
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        input.clone().draw_to_last_winner().expect("every card wins, checked when parsing")
+    }
+}
+
+aoc_utils::example_tests!(Day04);
+
+#[cfg(test)]
+const TEST_INPUT: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_read_input() {
+        let input = BingoInput::from_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            vec![
+                7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8,
+                19, 3, 26, 1
+            ],
+            input.drawn_numbers
+        );
+        assert_eq!(3, input.cards.len());
+        assert_eq!(vec![22, 13, 17, 11,  0], input.cards[0].row(0));
+        assert_eq!(vec![14, 21, 16, 12, 6], input.cards[1].row(4));
+    }
+
+    #[test]
+    fn test_read_input_line_endings() {
+        for input in aoc_utils::line_ending_variants(TEST_INPUT) {
+            let input = BingoInput::from_input(&input).unwrap();
+            assert_eq!(27, input.drawn_numbers.len());
+            assert_eq!(3, input.cards.len());
+            assert_eq!(vec![2, 0, 12, 3, 7], input.cards[2].row(4));
+        }
+    }
+
+    #[test]
+    fn test_read_malformed_input() {
+        let err = BingoInput::from_input(&TEST_INPUT.replace("7,4,9", "7,x,9")).err().unwrap();
+        assert_eq!("line 1, column 3: expected number, found 'x,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1'", err.to_string());
+        let err = BingoInput::from_input(&TEST_INPUT.replace(" 8  2 23  4 24", " 8  2 23  4")).err().unwrap();
+        assert_eq!("line 4, column 1: expected five numbers, found 4", err.to_string());
+        let err = BingoInput::from_input(&TEST_INPUT.replace(" 1 12 20 15 19\n", "")).err().unwrap();
+        assert_eq!("line 3, column 1: expected five rows, found 4", err.to_string());
+        let err = BingoInput::from_input(&TEST_INPUT.replacen(TEST_INPUT.lines().next().unwrap(), "14,21,17,24,4", 1)).err().unwrap();
+        assert_eq!("line 3, column 1: card never wins with the drawn numbers", err.to_string());
+    }
+
+    #[test]
+    fn test_winner_row() {
+        let card = Grid::from_fn(5, 5, |(_, row)| row == 1);
+        assert!(check_win(&card));
+
+        let card = Grid::from_fn(5, 5, |(_, row)| row == 0);
+        assert!(check_win(&card));
+    }
+
+    #[test]
+    fn test_winner_col() {
+        let card = Grid::from_fn(5, 5, |(col, _)| col == 1);
+        assert!(check_win(&card));
+
+        let card = Grid::from_fn(5, 5, |(col, _)| col == 4);
+        assert!(check_win(&card));
+    }
+
+    #[test]
+    fn test_no_winner() {
+        let card = Grid::filled(5, 5, false);
+        assert!(!check_win(&card));
+    }
+
+    #[test]
+    fn test_drawing() {
+        let input = BingoInput::from_input(TEST_INPUT).unwrap();
+        assert_eq!(Some(4512), input.draw());
+    }
+
+    #[test]
+    fn test_drawing_without_winner() {
+        let mut input = BingoInput::from_input(TEST_INPUT).unwrap();
+        input.drawn_numbers.truncate(5);
+        assert_eq!(None, input.draw());
+        assert_eq!(None, input.draw_to_last_winner());
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_drawing() {
+        let input = BingoInput::from_input(TEST_INPUT).unwrap();
+        assert_eq!(Some(1924), input.draw_to_last_winner());
+    }
+}
@@ -0,0 +1,135 @@
+//! Animation of the vents being drawn onto the ocean floor
+
+use std::collections::HashMap;
+
+use aoc_utils::{Animation, Frame, Grid, Palette, ParseError, Rgb, Solution, Visualization};
+
+use crate::{bounding_box, to_points, Day05, Line, Point};
+
+/// Shows the lines of star 2 one by one
+pub const VISUALIZATION: Visualization = Visualization {
+    day: 5,
+    description: "lines of vents drawn one by one, overlaps highlighted",
+    create,
+};
+
+/// Number of columns the ocean floor is scaled down to
+pub const MAX_WIDTH: usize = 120;
+
+fn create(input: &str) -> Result<Box<dyn Animation>, ParseError> {
+    Ok(Box::new(Vents::new(Day05::parse(input)?, MAX_WIDTH)))
+}
+
+/// Ocean floor with the lines drawn so far
+///
+/// Floors wider than `max_width` are scaled down, a character then shows the block of points with the most
+/// lines. Blocks are twice as high as wide to make up for the shape of terminal characters.
+pub struct Vents {
+    lines: Vec<Line>,
+    drawn: usize,
+    counts: HashMap<Point, usize>,
+    overlaps: usize,
+    origin: Point,
+    scale: (usize, usize),
+    blocks: Grid<usize>,
+}
+
+impl Vents {
+    /// Starts with an empty ocean floor covering the bounding box of the lines
+    pub fn new(lines: Vec<Line>, max_width: usize) -> Self {
+        let (origin, max) = bounding_box(&lines).unwrap_or_default();
+        let size = max - origin + Point::new(1, 1);
+        let (width, height) = (size.x as usize, size.y as usize);
+
+        let scale_x = width.div_ceil(max_width.max(1));
+        let scale = if scale_x > 1 { (scale_x, 2 * scale_x) } else { (1, 1) };
+        let blocks = Grid::filled(width.div_ceil(scale.0), height.div_ceil(scale.1), 0);
+        Vents {
+            lines,
+            drawn: 0,
+            counts: HashMap::new(),
+            overlaps: 0,
+            origin,
+            scale,
+            blocks,
+        }
+    }
+}
+
+impl Animation for Vents {
+    fn frame(&self) -> Frame {
+        let title = format!("Line {} of {}, {} points with overlap", self.drawn, self.lines.len(), self.overlaps);
+        let floor = self.blocks.map(|count| match count {
+            0 => '.',
+            1..=9 => char::from_digit(*count as u32, 10).unwrap(),
+            _ => '#',
+        });
+        Frame::new(title, &floor.to_string())
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(line) = self.lines.get(self.drawn) else {
+            return false;
+        };
+
+        for point in to_points(std::iter::once(*line)) {
+            let count = self.counts.entry(point).or_insert(0);
+            *count += 1;
+            if *count == 2 {
+                self.overlaps += 1;
+            }
+
+            let offset = point - self.origin;
+            let block = &mut self.blocks[(offset.x as usize / self.scale.0, offset.y as usize / self.scale.1)];
+            *block = (*block).max(*count);
+        }
+
+        self.drawn += 1;
+        true
+    }
+
+    fn palette(&self) -> Palette {
+        &[
+            ('.', Rgb(40, 60, 90)),
+            ('1', Rgb(90, 140, 200)),
+            ('2', Rgb(250, 210, 80)),
+            ('3', Rgb(250, 150, 60)),
+            ('4', Rgb(240, 90, 50)),
+            ('5', Rgb(240, 60, 60)),
+            ('6', Rgb(240, 60, 60)),
+            ('7', Rgb(240, 60, 60)),
+            ('8', Rgb(240, 60, 60)),
+            ('9', Rgb(240, 60, 60)),
+            ('#', Rgb(240, 60, 60)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests_animation {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn draw_lines() {
+        let mut animation = Vents::new(Day05::parse(TEST_INPUT).unwrap(), MAX_WIDTH);
+        assert_eq!("Line 0 of 10, 0 points with overlap", animation.frame().title);
+        assert_eq!(vec![".........."; 10], animation.frame().lines);
+
+        while animation.step() {}
+        let frame = animation.frame();
+        assert_eq!("Line 10 of 10, 12 points with overlap", frame.title);
+        assert_eq!(
+            "1.1....11.\n.111...2..\n..2.1.111.\n...1.2.2..\n.112313211\n...1.2....\n..1...1...\n.1.....1..\n1.......1.\n222111....",
+            frame.lines.join("\n")
+        );
+    }
+
+    #[test]
+    fn scale_down() {
+        let mut animation = Vents::new(Day05::parse(TEST_INPUT).unwrap(), 5);
+        while animation.step() {}
+        // Blocks of 2x4 points, the last row only covers two rows of points
+        assert_eq!(vec!["12221", "12331", "221.1"], animation.frame().lines);
+    }
+}
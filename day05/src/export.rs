@@ -0,0 +1,47 @@
+//! Heatmap of the vents
+
+use aoc_utils::{encode_pgm, Grid, ImageExport, ParseError, Solution};
+
+use crate::{bounding_box, count_points, to_points, Day05, Line, Point};
+
+/// Exports the overlaps of all lines (star 2)
+pub const EXPORT: ImageExport = ImageExport {
+    day: 5,
+    extension: "pgm",
+    description: "heatmap of the vents, the more lines cover a point the brighter it is",
+    render,
+};
+
+fn render(input: &str) -> Result<Vec<u8>, ParseError> {
+    Ok(encode_pgm(&heatmap(&Day05::parse(input)?)))
+}
+
+/// One pixel per point of the bounding box of the lines, black if not covered and white for the most covered points
+pub fn heatmap(lines: &[Line]) -> Grid<u8> {
+    let Some((origin, max)) = bounding_box(lines) else {
+        return Grid::filled(0, 0, 0);
+    };
+
+    let counts = count_points(to_points(lines.iter().copied()));
+    let brightest = counts.values().copied().max().unwrap_or(1);
+    let size = max - origin;
+    Grid::from_fn(size.x as usize + 1, size.y as usize + 1, |(x, y)| {
+        let point = origin + Point::new(x as i16, y as i16);
+        (counts.get(&point).copied().unwrap_or(0) * 255 / brightest) as u8
+    })
+}
+
+#[cfg(test)]
+mod tests_export {
+    use super::*;
+    use crate::TEST_INPUT;
+
+    #[test]
+    fn export() {
+        let heatmap = heatmap(&Day05::parse(TEST_INPUT).unwrap());
+        assert_eq!((10, 10), (heatmap.width(), heatmap.height()));
+        // Row 4 of the diagram in the puzzle is `.112313211` with at most 3 lines per point
+        assert_eq!(&[0, 85, 85, 170, 255, 85, 255, 170, 85, 85], heatmap.row(4));
+        assert!(render(TEST_INPUT).unwrap().starts_with(b"P5\n10 10\n255\n"));
+    }
+}
@@ -0,0 +1,53 @@
+//! Random inputs for day 5
+
+use aoc_utils::{InputGenerator, Rng};
+
+/// Generates lines of hydrothermal vents, `size` is the number of lines
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 5,
+    size: "number of lines",
+    default_size: 500,
+    generate,
+};
+
+/// Generates `size` horizontal, vertical and diagonal lines on an ocean floor of 1000x1000 points
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lines = Vec::with_capacity(size);
+    for _ in 0..size {
+        let (x1, y1) = (rng.range(0..=999), rng.range(0..=999));
+        let (x2, y2) = match rng.below(3) {
+            0 => (rng.range(0..=999), y1),
+            1 => (x1, rng.range(0..=999)),
+            _ => {
+                // Diagonal at 45 degrees, limited by the edges of the floor in the chosen direction
+                let (dx, dy) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+                let max_x = if dx > 0 { 999 - x1 } else { x1 };
+                let max_y = if dy > 0 { 999 - y1 } else { y1 };
+                let length = rng.range(0..=max_x.min(max_y));
+                (x1 + dx * length, y1 + dy * length)
+            }
+        };
+
+        lines.push(format!("{},{} -> {},{}", x1, y1, x2, y2));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+    use crate::Day05;
+    use aoc_utils::{read_lines, Solution, StreamingSolution};
+
+    #[test]
+    fn test_generate() {
+        let input = GENERATOR.generate(5, Some(200));
+        let lines = Day05::parse(&input).unwrap();
+        assert_eq!(200, lines.len());
+        assert!(lines.iter().all(|l| l.is_straight() || (l.start.x - l.end.x).abs() == (l.start.y - l.end.y).abs()));
+
+        let streamed = Day05::solve_lines(read_lines(input.as_bytes()).map(Result::unwrap)).unwrap();
+        assert_eq!((Day05::part1(&lines), Day05::part2(&lines)), streamed);
+    }
+}
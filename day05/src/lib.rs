@@ -0,0 +1,256 @@
+//! Day 5: Hydrothermal Venture
+#![warn(missing_docs)]
+
+use std::{collections::HashMap, iter};
+
+use aoc_utils::{parse_lines, LineParser, ParseError, Point2, Solution, StreamingSolution};
+use genawaiter::{sync::gen, yield_};
+use itertools::Itertools;
+
+pub mod animation;
+pub mod export;
+pub mod generator;
+
+/// Point on the ocean floor
+pub type Point = Point2<i16>;
+
+/// Line of hydrothermal vents (horizontal, vertical or diagonal at 45 degrees)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Line {
+    /// First point of the line
+    pub start: Point,
+    /// Last point of the line
+    pub end: Point,
+}
+
+impl Line {
+    /// Parses a line like `0,9 -> 5,9`
+    ///
+    /// Fails if the line is neither horizontal, vertical nor diagonal at 45 degrees.
+    pub fn parse(line: &mut LineParser) -> Result<Self, ParseError> {
+        let start = Point::parse(line)?;
+        line.literal(" -> ")?;
+        let column = line.column();
+        let result = Line { start, end: Point::parse(line)? };
+        if !result.is_straight() && (i32::from(result.end.x) - i32::from(start.x)).abs() != (i32::from(result.end.y) - i32::from(start.y)).abs() {
+            return Err(ParseError::new(line.line(), column, "line is neither horizontal, vertical nor diagonal at 45 degrees"));
+        }
+
+        Ok(result)
+    }
+
+    /// Whether the line is horizontal or vertical
+    pub fn is_straight(&self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+}
+
+/// Parses the lines of vents, one per line of input
+pub fn parse_input(input: &str) -> Result<Vec<Line>, ParseError> {
+    parse_lines(input, Line::parse)
+}
+
+/// Smallest and largest coordinates of the lines, `None` if there are no lines
+pub fn bounding_box(lines: &[Line]) -> Option<(Point, Point)> {
+    let points = lines.iter().flat_map(|l| [l.start, l.end]);
+    let min = Point::new(points.clone().map(|p| p.x).min()?, points.clone().map(|p| p.y).min()?);
+    Some((min, Point::new(points.clone().map(|p| p.x).max()?, points.map(|p| p.y).max()?)))
+}
+
+/// Keeps only horizontal and vertical lines
+pub fn filter_only_straight(input: impl Iterator<Item = Line>) -> impl Iterator<Item = Line> {
+    input.filter(Line::is_straight)
+}
+
+/// Iterates over all points covered by the lines (including start and end)
+pub fn to_points(input: impl Iterator<Item = Line>) -> impl Iterator<Item = Point> {
+    input.flat_map(|l| {
+        gen!({
+            // Lines are horizontal, vertical or diagonal at 45 degrees, so each step changes x and y by at most 1
+            let step = (l.end - l.start).signum();
+            let mut p = l.start;
+            loop {
+                yield_!(p);
+                if p == l.end {
+                    break;
+                }
+
+                p += step;
+            }
+        })
+    })
+}
+
+/// Counts how many times each point is covered
+pub fn count_points(input: impl Iterator<Item = Point>) -> HashMap<Point, usize> {
+    input.counts_by(|p| p)
+}
+
+/// Counts the points that are covered at least twice
+pub fn count_overlap_points(input: HashMap<Point, usize>) -> usize {
+    input.into_values().filter(|v| *v >= 2).count()
+}
+
+/// Solution for day 5
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: i32 = 5;
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        let points_stat = count_points(to_points(filter_only_straight(input.iter().cloned())));
+        count_overlap_points(points_stat)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        let points_stat = count_points(to_points(input.iter().cloned()));
+        count_overlap_points(points_stat)
+    }
+}
+
+impl StreamingSolution for Day05 {
+    fn solve_lines(lines: impl Iterator<Item = (usize, String)>) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        // Only the covered points are kept, their number is limited by the size of the ocean floor
+        let (mut straight, mut all) = (HashMap::new(), HashMap::new());
+        for (number, text) in lines {
+            let mut parser = LineParser::new(number, &text);
+            let line = Line::parse(&mut parser)?;
+            parser.end()?;
+
+            for point in to_points(iter::once(line)) {
+                if line.is_straight() {
+                    *straight.entry(point).or_insert(0) += 1;
+                }
+
+                *all.entry(point).or_insert(0) += 1;
+            }
+        }
+
+        Ok((count_overlap_points(straight), count_overlap_points(all)))
+    }
+}
+
+aoc_utils::example_tests!(Day05);
+
+#[cfg(test)]
+const TEST_INPUT: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(0, input[0].start.x);
+        assert_eq!(0, input[1].start.y);
+        assert_eq!(3, input[2].end.x);
+        assert_eq!(1, input[3].end.y);
+    }
+
+    #[test]
+    fn test_parse_malformed_input() {
+        let err = parse_input("0,9 -> 5,9\n8,0 => 0,8").unwrap_err();
+        assert_eq!("line 2, column 4: expected ' -> ', found '=>'", err.to_string());
+    }
+
+    #[test]
+    fn test_parse_skewed_line() {
+        let err = parse_input("0,0 -> 3,3\n0,0 -> 3,1").unwrap_err();
+        assert_eq!("line 2, column 8: line is neither horizontal, vertical nor diagonal at 45 degrees", err.to_string());
+        assert!(parse_input("5,5 -> 8,2\n-32768,0 -> 32767,0").is_ok());
+    }
+
+    #[test]
+    fn test_filter() {
+        let input: Vec<Line> = vec![
+            Line {
+                start: Point { x: 0, y: 0 },
+                end: Point { x: 0, y: 2 },
+            },
+            Line {
+                start: Point { x: 0, y: 0 },
+                end: Point { x: 1, y: 1 },
+            },
+        ];
+        let input = filter_only_straight(input.into_iter());
+        assert_eq!(1, input.count());
+    }
+
+    #[test]
+    fn test_to_points() {
+        let input = filter_only_straight(parse_input(TEST_INPUT).unwrap().into_iter());
+        let input = to_points(input.into_iter());
+        assert_eq!(26, input.count());
+    }
+
+    #[test]
+    fn test_count_points() {
+        let input: Vec<Point> = vec![
+            Point { x: 0, y: 0 },
+            Point { x: 0, y: 2 },
+            Point { x: 0, y: 0 },
+            Point { x: 0, y: 2 },
+        ];
+        let input = count_points(input.into_iter());
+        assert_eq!(2, input[&Point { x: 0, y: 0 }]);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let lines = parse_input("3,9 -> 5,9\n8,2 -> 1,2").unwrap();
+        assert_eq!(Some((Point::new(1, 2), Point::new(8, 9))), bounding_box(&lines));
+        assert_eq!(None, bounding_box(&[]));
+    }
+
+    #[test]
+    fn test_1() {
+        let points_stat = count_points(to_points(filter_only_straight(parse_input(TEST_INPUT).unwrap().into_iter())));
+        assert_eq!(5, count_overlap_points(points_stat));
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_to_points() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let input = to_points(input.into_iter());
+        assert_eq!(53, input.count());
+    }
+
+    #[test]
+    fn test_2() {
+        let points_stat = count_points(to_points(parse_input(TEST_INPUT).unwrap().into_iter()));
+        assert_eq!(12, count_overlap_points(points_stat));
+    }
+
+    #[test]
+    fn test_streaming() {
+        let lines = aoc_utils::read_lines(TEST_INPUT.as_bytes()).map(Result::unwrap);
+        assert_eq!((5, 12), Day05::solve_lines(lines).unwrap());
+
+        let lines = aoc_utils::read_lines("0,9 -> 5,9\n8,0 => 0,8".as_bytes()).map(Result::unwrap);
+        assert_eq!("line 2, column 4: expected ' -> ', found '=>'", Day05::solve_lines(lines).unwrap_err().to_string());
+    }
+}
@@ -0,0 +1,121 @@
+//! Day 6: Lanternfish
+#![warn(missing_docs)]
+
+use aoc_utils::{parse_line, ParseError, Solution};
+
+/// Parses the comma separated timers and counts the fishes per timer value (index 0 to 8)
+pub fn parse_input(input: &str) -> Result<[u64; 9], ParseError> {
+    let timers = parse_line(input, |l| {
+        l.separated(",", |l| {
+            let column = l.column();
+            match l.number::<usize>()? {
+                timer @ 0..=8 => Ok(timer),
+                timer => Err(ParseError::new(l.line(), column, format!("invalid timer {}, expected 0 to 8", timer))),
+            }
+        })
+    })?;
+
+    let mut fishes = [0u64; 9];
+    for f in timers {
+        fishes[f] += 1;
+    }
+
+    Ok(fishes)
+}
+
+/// Simulates the given number of days, returns the number of fishes
+pub fn calculate(mut fishes: [u64; 9], iterations: usize) -> u64 {
+    for _ in 0..iterations {
+        let mut new_fishes = [0; 9];
+        new_fishes[6] = fishes[0];
+        new_fishes[8] = fishes[0];
+        for j in 1..9 {
+            new_fishes[j - 1] += fishes[j];
+        }
+
+        fishes = new_fishes;
+    }
+
+    fishes.into_iter().sum()
+}
+
+/// Solution for day 6
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: i32 = 6;
+    type Input = [u64; 9];
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(fishes: &Self::Input) -> Self::Answer1 {
+        calculate(*fishes, 80)
+    }
+
+    fn part2(fishes: &Self::Input) -> Self::Answer2 {
+        calculate(*fishes, 256)
+    }
+}
+
+aoc_utils::example_tests!(Day06);
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let fishes = parse_input("3,4,3,1,2").unwrap();
+        assert_eq!(26, calculate(fishes, 18));
+        assert_eq!(5934, calculate(fishes, 80));
+    }
+
+    #[test]
+    fn test_parse_invalid_timer() {
+        let err = parse_input("3,4,9,1").unwrap_err();
+        assert_eq!("line 1, column 5: invalid timer 9, expected 0 to 8", err.to_string());
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_2() {
+        let fishes = parse_input("3,4,3,1,2").unwrap();
+        assert_eq!(26984457539, calculate(fishes, 256));
+    }
+}
+
+/// Property tests against a naive simulation
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Simulates every fish on its own
+    fn simulate(mut timers: Vec<u8>, days: usize) -> u64 {
+        for _ in 0..days {
+            let new_fishes = timers.iter().filter(|t| **t == 0).count();
+            timers.iter_mut().for_each(|t| *t = if *t == 0 { 6 } else { *t - 1 });
+            timers.extend(std::iter::repeat_n(8, new_fishes));
+        }
+
+        timers.len() as u64
+    }
+
+    proptest! {
+        #[test]
+        fn calculate_matches_simulation(timers in prop::collection::vec(0u8..=8, 1..10), days in 0usize..=80) {
+            let input = timers.iter().map(u8::to_string).collect::<Vec<_>>().join(",");
+            prop_assert_eq!(simulate(timers, days), calculate(parse_input(&input).unwrap(), days));
+        }
+    }
+}
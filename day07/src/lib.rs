@@ -0,0 +1,157 @@
+//! Day 7: The Treachery of Whales
+#![warn(missing_docs)]
+
+use aoc_utils::{parse_line, LineParser, ParseError, Solution};
+
+/// Parses the comma separated crab positions, sorted ascending
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    let mut values = parse_line(input, |l| l.separated(",", LineParser::number))?;
+    values.sort_unstable();
+    Ok(values)
+}
+
+/// Fuel to align the crabs with constant fuel rate (star 1)
+///
+/// Expects sorted positions, the cheapest position is the median.
+pub fn calculate_fuel(values: &[i32]) -> i32 {
+    let median = values[values.len() / 2];
+    values
+        .iter()
+        .cloned()
+        .fold(0, |acc, v| acc + (v - median).abs())
+}
+
+/// Fuel for a single crab to move from `p1` to `p2` if each step costs one more than the previous one
+pub fn calculate_fuel_2(p1: i32, p2: i32) -> i32 {
+    (p2 - p1).abs() * ((p2 - p1).abs() + 1) / 2
+}
+
+/// Fuel for all crabs to move to `p` with increasing fuel rate
+pub fn calculate_total_fuel_2(values: &[i32], p: i32) -> i32 {
+    values
+        .iter()
+        .cloned()
+        .fold(0, |acc, v| acc + calculate_fuel_2(p, v))
+}
+
+/// Lowest fuel to align the crabs with increasing fuel rate (star 2)
+pub fn find_lowest_fuel(values: &[i32]) -> i32 {
+    let mut up = true;
+    let mut down = true;
+    let pos = values.iter().sum::<i32>() / values.len() as i32;
+    let mut min_fuel = calculate_total_fuel_2(values, pos);
+    let mut distance = 1;
+    loop {
+        if up {
+            let up_fuel = calculate_total_fuel_2(values, pos + distance);
+            if up_fuel < min_fuel {
+                min_fuel = up_fuel;
+                down = false;
+            } else {
+                up = false
+            }
+        }
+
+        if down {
+            let down_fuel = calculate_total_fuel_2(values, pos - distance);
+            if down_fuel < min_fuel {
+                min_fuel = down_fuel;
+                up = false;
+            } else {
+                down = false;
+            }
+        }
+
+        if !up && !down {
+            break;
+        }
+
+        distance += 1;
+    }
+
+    min_fuel
+}
+
+/// Solution for day 7
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: i32 = 7;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(values: &Self::Input) -> Self::Answer1 {
+        calculate_fuel(values)
+    }
+
+    fn part2(values: &Self::Input) -> Self::Answer2 {
+        find_lowest_fuel(values)
+    }
+}
+
+aoc_utils::example_tests!(Day07);
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let values = parse_input("16,1,2,0,4,2,7,1,2,14").unwrap();
+        assert_eq!(37, calculate_fuel(&values));
+    }
+
+    #[test]
+    fn test_calculate_fuel_2() {
+        assert_eq!(66, calculate_fuel_2(16, 5));
+        assert_eq!(66, calculate_fuel_2(5, 16));
+        assert_eq!(0, calculate_fuel_2(5, 5));
+        assert_eq!(1, calculate_fuel_2(4, 5));
+        assert_eq!(1, calculate_fuel_2(5, 4));
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_2() {
+        let values = parse_input("16,1,2,0,4,2,7,1,2,14").unwrap();
+        assert_eq!(168, find_lowest_fuel(&values))
+    }
+}
+
+/// Property tests against an exhaustive scan of all positions
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Lowest fuel of all positions between the outermost crabs
+    fn scan(values: &[i32], fuel: impl Fn(i32, i32) -> i32) -> i32 {
+        let (min, max) = (*values.iter().min().unwrap(), *values.iter().max().unwrap());
+        (min..=max).map(|p| values.iter().map(|v| fuel(p, *v)).sum()).min().unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn calculate_fuel_matches_scan(mut values in prop::collection::vec(0i32..=2000, 1..50)) {
+            values.sort_unstable();
+            prop_assert_eq!(scan(&values, |p, v| (p - v).abs()), calculate_fuel(&values));
+        }
+
+        #[test]
+        fn find_lowest_fuel_matches_scan(mut values in prop::collection::vec(0i32..=2000, 1..50)) {
+            values.sort_unstable();
+            prop_assert_eq!(scan(&values, calculate_fuel_2), find_lowest_fuel(&values));
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Random inputs for day 16
+
+use aoc_utils::{InputGenerator, Rng};
+
+use crate::{Header, Literal, Operator, Packet};
+
+/// Generates BITS transmissions, `size` is the number of packets
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 16,
+    size: "number of packets",
+    default_size: 250,
+    generate,
+};
+
+/// Generates a transmission with `size` packets (at least 1)
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    encode(&random_packet(rng, size.max(1)))
+}
+
+/// Random packet with `size` packets in total, including itself
+///
+/// The value of the expression fits into a `u64`: products only have up to 5 single digit literals as operands.
+pub fn random_packet(rng: &mut Rng, size: usize) -> Packet {
+    let version = rng.below(8) as u8;
+    let operands = size - 1;
+    if operands == 0 {
+        let bits = rng.range(1..=36);
+        return Packet::Literal(Literal {
+            header: Header { version, type_id: 4 },
+            value: rng.below(1 << bits),
+        });
+    }
+
+    let type_id = loop {
+        let type_id = *rng.choose(&[0, 1, 2, 3, 5, 6, 7]);
+        match type_id {
+            1 if operands > 5 => continue,
+            5..=7 if operands < 2 => continue,
+            _ => break type_id,
+        }
+    };
+
+    let packets = match type_id {
+        1 => (0..operands)
+            .map(|_| {
+                Packet::Literal(Literal {
+                    header: Header { version: rng.below(8) as u8, type_id: 4 },
+                    value: rng.below(10),
+                })
+            })
+            .collect(),
+        _ => {
+            let count = if type_id >= 5 { 2 } else { rng.range(1..=operands.min(5) as i64) as usize };
+            split(rng, operands, count).into_iter().map(|size| random_packet(rng, size)).collect()
+        }
+    };
+
+    Packet::Operator(Operator {
+        header: Header { version, type_id },
+        packets,
+    })
+}
+
+/// Splits `total` into `count` random parts of at least 1
+fn split(rng: &mut Rng, total: usize, count: usize) -> Vec<usize> {
+    let mut parts = vec![1; count];
+    for _ in count..total {
+        parts[rng.below(count as u64) as usize] += 1;
+    }
+
+    parts
+}
+
+/// Encodes a packet as hexadecimal transmission, padded with zeros to whole bytes
+///
+/// Operators use the length in bits if it fits into 15 bits and the number of sub-packets otherwise.
+pub fn encode(packet: &Packet) -> String {
+    let mut bits = Vec::new();
+    encode_bits(packet, &mut bits);
+    bits.resize(bits.len().div_ceil(8) * 8, false);
+    bits.chunks(4).map(|nibble| format!("{:X}", nibble.iter().fold(0, |value, bit| value << 1 | *bit as u8))).collect()
+}
+
+fn push_number(bits: &mut Vec<bool>, value: u64, width: usize) {
+    bits.extend((0..width).rev().map(|bit| value >> bit & 1 == 1));
+}
+
+fn encode_bits(packet: &Packet, bits: &mut Vec<bool>) {
+    match packet {
+        Packet::Literal(literal) => {
+            push_number(bits, literal.header.version as u64, 3);
+            push_number(bits, 4, 3);
+            let groups = (64 - literal.value.leading_zeros() as usize).max(1).div_ceil(4);
+            for group in (0..groups).rev() {
+                bits.push(group > 0);
+                push_number(bits, literal.value >> (group * 4) & 0xf, 4);
+            }
+        }
+        Packet::Operator(operator) => {
+            push_number(bits, operator.header.version as u64, 3);
+            push_number(bits, operator.header.type_id as u64, 3);
+            let mut operands = Vec::new();
+            operator.packets.iter().for_each(|packet| encode_bits(packet, &mut operands));
+            if operands.len() < 1 << 15 {
+                bits.push(false);
+                push_number(bits, operands.len() as u64, 15);
+            } else {
+                bits.push(true);
+                push_number(bits, operator.packets.len() as u64, 11);
+            }
+
+            bits.extend(operands);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+
+    fn count(packet: &Packet) -> usize {
+        match packet {
+            Packet::Literal(_) => 1,
+            Packet::Operator(o) => 1 + o.packets.iter().map(count).sum::<usize>(),
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!("D2FE28", encode(&Packet::decode("D2FE28").unwrap()));
+        assert_eq!("38006F45291200", encode(&Packet::decode("38006F45291200").unwrap()));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let packet = random_packet(&mut Rng::new(seed), 100);
+            assert_eq!(100, count(&packet));
+            assert_eq!(packet, Packet::decode(&encode(&packet)).unwrap());
+            packet.evaluate();
+        }
+
+        assert_eq!(1, count(&Packet::decode(&GENERATOR.generate(1, Some(1))).unwrap()));
+    }
+}
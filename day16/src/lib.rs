@@ -0,0 +1,458 @@
+//! Day 16: Packet Decoder
+#![warn(missing_docs)]
+
+use aoc_utils::{trace_event, ParseError, Solution};
+use bitvec::prelude::*;
+
+pub mod generator;
+
+/// Converts the hexadecimal transmission into bytes
+pub fn parse_input(input: &[u8]) -> Result<Vec<u8>, ParseError> {
+    fn char_to_byte(c: u8, ix: usize) -> Result<u8, ParseError> {
+        match c {
+            digit if digit.is_ascii_digit() => Ok(digit - b'0'),
+            digit if (b'A'..=b'F').contains(&digit) => Ok(digit - b'A' + 10),
+            _ => Err(ParseError::new(1, ix + 1, format!("expected hexadecimal digit, found '{}'", c.escape_ascii())))
+        }
+    }
+
+    let number_of_bytes = input.len() / 2;
+    let mut result = Vec::<u8>::with_capacity(number_of_bytes);
+    for (n, digits) in input.chunks(2).enumerate() {
+        let high = char_to_byte(digits[0], n * 2)?;
+        let Some(&low) = digits.get(1) else {
+            return Err(ParseError::new(1, input.len(), "expected an even number of hexadecimal digits"));
+        };
+        result.push(high << 4 | char_to_byte(low, n * 2 + 1)?);
+    }
+
+    Ok(result)
+}
+
+/// Header of a packet
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Header {
+    /// Packet version (3 bits)
+    pub version: u8,
+    /// Packet type (3 bits), 4 is a literal value, all other types are operators
+    pub type_id: u8,
+}
+
+/// Packet that applies an operation to its sub-packets
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Operator {
+    /// Header, the type id selects the operation
+    pub header: Header,
+    /// Sub-packets (operands)
+    pub packets: Vec<Packet>,
+}
+
+/// Packet with a literal value
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Literal {
+    /// Header (type id 4)
+    pub header: Header,
+    /// Decoded value
+    pub value: u64,
+}
+
+/// Packet of the BITS transmission
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Packet {
+    /// Literal value
+    Literal(Literal),
+    /// Operation on sub-packets
+    Operator(Operator),
+}
+
+impl Packet {
+    /// Decodes the outermost packet of a hexadecimal transmission (surrounding whitespace like a final line break is ignored)
+    pub fn decode(hex: &str) -> Result<Packet, ParseError> {
+        let values = parse_input(hex.trim().as_bytes())?;
+        let bits = values.view_bits::<Msb0>();
+
+        let mut bit_index = 0usize;
+        dispatch(bits, &mut bit_index)
+    }
+
+    /// Sum of the versions of this packet and all sub-packets
+    pub fn version_sum(&self) -> u32 {
+        match self {
+            Packet::Literal(v) => v.header.version as u32,
+            Packet::Operator(o) => o.header.version as u32 + o.packets.iter().map(|o| o.version_sum()).sum::<u32>(),
+        }
+    }
+
+    /// Calculates the value of the expression represented by the packet
+    pub fn evaluate(&self) -> u64 {
+        match self {
+            Packet::Literal(v) => v.value,
+            Packet::Operator(o) => match o.header.type_id {
+                0 => o.packets.iter().map(|o| o.evaluate()).sum::<u64>(),
+                1 => o.packets.iter().map(|o| o.evaluate()).product(),
+                2 => o.packets.iter().map(|o| o.evaluate()).min().unwrap(),
+                3 => o.packets.iter().map(|o| o.evaluate()).max().unwrap(),
+                5 => if o.packets[0].evaluate() > o.packets[1].evaluate() { 1 } else { 0 },
+                6 => if o.packets[0].evaluate() < o.packets[1].evaluate() { 1 } else { 0 },
+                7 => if o.packets[0].evaluate() == o.packets[1].evaluate() { 1 } else { 0 },
+                _ => panic!("Invalid type id")
+            },
+        }
+    }
+}
+
+/// Error at the hexadecimal digit that contains the given bit
+fn error_at(bit_index: usize, message: impl Into<String>) -> ParseError {
+    ParseError::new(1, bit_index / 4 + 1, message)
+}
+
+/// Reads the next `count` bits (at most 16) as a number
+fn read_bits(bits: &BitSlice::<Msb0, u8>, bit_index: &mut usize, count: usize) -> Result<u16, ParseError> {
+    if *bit_index + count > bits.len() {
+        return Err(error_at(bits.len(), "transmission ends in the middle of a packet"));
+    }
+
+    let value = bits[*bit_index..*bit_index + count].load_be();
+    *bit_index += count;
+    Ok(value)
+}
+
+fn read_header(bits: &BitSlice::<Msb0, u8>, bit_index: &mut usize) -> Result<Header, ParseError> {
+    Ok(Header{
+        version: read_bits(bits, bit_index, 3)? as u8,
+        type_id: read_bits(bits, bit_index, 3)? as u8,
+    })
+}
+
+fn read_literal(header: Header, bits: &BitSlice::<Msb0, u8>, bit_index: &mut usize) -> Result<Packet, ParseError> {
+    let mut value = 0u64;
+    let start_index = *bit_index;
+
+    loop {
+        // Read 4 bits of literal and combine it with existing value
+        let chunk = read_bits(bits, bit_index, 5)?;
+        if value >> 60 != 0 {
+            return Err(error_at(start_index, "literal value does not fit into 64 bits"));
+        }
+        value = value << 4 | (chunk & 0xf) as u64;
+
+        // Check if there are additional 4 bit chunks
+        if chunk & 0x10 == 0 {
+            // No additional chunks -> stop
+            break;
+        }
+    }
+
+    trace_event!("literal", version = header.version, value = value, bits = *bit_index - start_index);
+
+    // Return literal
+    Ok(Packet::Literal(Literal{header, value}))
+}
+
+fn read_operator(header: Header, bits: &BitSlice::<Msb0, u8>, bit_index: &mut usize) -> Result<Packet, ParseError> {
+    let start_index = *bit_index;
+
+    // End conditions (one of them will be set)
+    let mut number_of_sub_packets = usize::MAX;
+    let mut length = usize::MAX;
+
+    // Interpret length type id
+    if read_bits(bits, bit_index, 1)? == 1 {
+        // Number of sub-packets
+        number_of_sub_packets = read_bits(bits, bit_index, 11)? as usize;
+    } else {
+        // Length in bits
+        length = read_bits(bits, bit_index, 15)? as usize;
+    }
+
+    // Create variable for receiving subpackets
+    let mut packets: Vec<Packet>;
+    if number_of_sub_packets != usize::MAX {
+        packets = Vec::with_capacity(number_of_sub_packets);
+    } else {
+        packets = Vec::new();
+    }
+    
+    let sub_packets_start = *bit_index;
+    while packets.len() < number_of_sub_packets && *bit_index - sub_packets_start < length {
+        packets.push(dispatch(bits, bit_index)?);
+    }
+
+    if length != usize::MAX && *bit_index - sub_packets_start != length {
+        return Err(error_at(start_index, format!("sub-packets take {} bits instead of {}", *bit_index - sub_packets_start, length)));
+    }
+
+    // Minimum and maximum need an operand, comparisons exactly two
+    let valid = match header.type_id {
+        2 | 3 => !packets.is_empty(),
+        5..=7 => packets.len() == 2,
+        _ => true,
+    };
+    if !valid {
+        return Err(error_at(start_index, format!("operator with type id {} can't have {} sub-packets", header.type_id, packets.len())));
+    }
+
+    trace_event!("operator", version = header.version, type_id = header.type_id, packets = packets.len());
+    Ok(Packet::Operator(Operator{header, packets}))
+}
+
+/// Reads the header and decides based on it what to read
+fn dispatch(bits: &BitSlice::<Msb0, u8>, bit_index: &mut usize) -> Result<Packet, ParseError> {
+    let header = read_header(bits, bit_index)?;
+    match header.type_id {
+        4 => read_literal(header, bits, bit_index),
+        _ => read_operator(header, bits, bit_index)
+    }
+}
+
+/// Solution for day 16
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: i32 = 16;
+    type Input = Packet;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Packet::decode(input)
+    }
+
+    fn part1(packet: &Self::Input) -> Self::Answer1 {
+        packet.version_sum()
+    }
+
+    fn part2(packet: &Self::Input) -> Self::Answer2 {
+        packet.evaluate()
+    }
+}
+
+aoc_utils::example_tests!(Day16);
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    const TEST_INPUT_SHORT: &[u8] = b"8A004A801A8002F478";
+
+    #[test]
+    fn test_parse() {
+        let result = parse_input(TEST_INPUT_SHORT).unwrap();
+        assert_eq!(vec![0x8A, 0x00, 0x4A, 0x80, 0x1A, 0x80, 0x02, 0xF4, 0x78], result);
+    }
+
+    
+    #[test]
+    fn test_header() {
+        let input = parse_input(b"D2FE28").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+        let mut bit_index = 0usize;
+        let h = read_header(bits, &mut bit_index).unwrap();
+        assert_eq!(4, h.type_id);
+        assert_eq!(6, h.version);
+    }
+
+    #[test]
+    fn test_literal() {
+        let input = parse_input(b"D2FE28").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let header = read_header(bits, &mut bit_index).unwrap();
+        let packet = read_literal(header.clone(), bits, &mut bit_index).unwrap();
+        assert_eq!(Packet::Literal(Literal{header, value: 2021}), packet);
+        assert_eq!(6, packet.version_sum());
+    }
+
+    macro_rules! try_parse_packet {
+        ($name:ident is $tpack:ident $(if $s:stmt)*) => {
+            let pack = $name;
+            let $name;
+            if let Packet::$tpack(p) = pack {
+                $name = p;
+                $($s)*
+            }
+            else {
+                assert!(false, "unexpected packet type");
+                return;
+            }
+        };
+    }
+    
+    #[test]
+    fn test_operator() {
+        let input = parse_input(b"38006F45291200").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+
+        try_parse_packet!(packet is Operator 
+            if assert_eq!(6, packet.header.type_id)
+            if assert_eq!(2, packet.packets.len()));
+        let mut packet_iter = packet.packets.into_iter();
+        let lit = packet_iter.next().unwrap();
+        try_parse_packet!(lit is Literal if assert_eq!(10, lit.value));
+        let lit = packet_iter.next().unwrap();
+        try_parse_packet!(lit is Literal if assert_eq!(20, lit.value));
+    }
+
+    #[test]
+    fn test_operator_2() {
+        let input = parse_input(b"EE00D40C823060").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+
+        try_parse_packet!(packet is Operator 
+            if assert_eq!(7, packet.header.version)
+            if assert_eq!(3, packet.header.type_id)
+            if assert_eq!(3, packet.packets.len()));
+        let mut packet_iter = packet.packets.into_iter();
+        let lit = packet_iter.next().unwrap();
+        try_parse_packet!(lit is Literal if assert_eq!(1, lit.value));
+        let lit = packet_iter.next().unwrap();
+        try_parse_packet!(lit is Literal if assert_eq!(2, lit.value));
+        let lit = packet_iter.next().unwrap();
+        try_parse_packet!(lit is Literal if assert_eq!(3, lit.value));
+    }
+
+    #[test]
+    fn test_nested_operator() {
+        let input = parse_input(b"8A004A801A8002F478").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+
+        try_parse_packet!(packet is Operator 
+            if assert_eq!(4, packet.header.version)
+            if assert_eq!(1, packet.packets.len()));
+        let packet = packet.packets.first().unwrap();
+        try_parse_packet!(packet is Operator 
+            if assert_eq!(1, packet.header.version)
+            if assert_eq!(1, packet.packets.len()));
+        let packet = packet.packets.first().unwrap();
+        try_parse_packet!(packet is Operator 
+            if assert_eq!(5, packet.header.version)
+            if assert_eq!(1, packet.packets.len()));
+        let lit = packet.packets.first().unwrap();
+        try_parse_packet!(lit is Literal if assert_eq!(6, lit.header.version));
+    }
+
+    #[test]
+    fn test_version_sum_1() {
+        let input = parse_input(b"8A004A801A8002F478").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+        assert_eq!(16, packet.version_sum())
+    }
+
+    #[test]
+    fn test_version_sum_2() {
+        let input = parse_input(b"620080001611562C8802118E34").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+        assert_eq!(12, packet.version_sum())
+    }
+
+    #[test]
+    fn test_version_sum_3() {
+        let input = parse_input(b"C0015000016115A2E0802F182340").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+        assert_eq!(23, packet.version_sum())
+    }
+
+    #[test]
+    fn test_version_sum_4() {
+        let input = parse_input(b"A0016C880162017C3686B18A3D4780").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+        assert_eq!(31, packet.version_sum())
+    }
+
+    #[test]
+    fn test_decode_line_endings() {
+        for input in aoc_utils::line_ending_variants("8A004A801A8002F478") {
+            assert_eq!(16, Packet::decode(&input).unwrap().version_sum());
+        }
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        let err = Packet::decode("D2FG28").unwrap_err();
+        assert_eq!("line 1, column 4: expected hexadecimal digit, found 'G'", err.to_string());
+        let err = Packet::decode("D2FE2").unwrap_err();
+        assert_eq!("line 1, column 5: expected an even number of hexadecimal digits", err.to_string());
+        let err = Packet::decode("D2FE").unwrap_err();
+        assert_eq!("line 1, column 5: transmission ends in the middle of a packet", err.to_string());
+
+        let literal = Packet::Literal(Literal{header: Header{version: 0, type_id: 4}, value: 1});
+        let comparison = Packet::Operator(Operator{header: Header{version: 0, type_id: 5}, packets: vec![literal; 3]});
+        let err = Packet::decode(&crate::generator::encode(&comparison)).unwrap_err();
+        assert_eq!("line 1, column 2: operator with type id 5 can't have 3 sub-packets", err.to_string());
+    }
+
+    #[test]
+    fn test_bit_load() {
+        let input = vec![0b10000001, 0b10110000];
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+        assert_eq!(27, bits[1..1 + 11].load_be::<u16>());
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_1() {
+        let input = parse_input(b"C200B40A82").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+        assert_eq!(3, packet.evaluate())
+    }
+
+    #[test]
+    fn test_evaluate_2() {
+        let input = parse_input(b"880086C3E88112").unwrap();
+        let bits = BitSlice::<Msb0, u8>::from_slice(&input).unwrap();
+
+        let mut bit_index = 0usize;
+        let packet = dispatch(bits, &mut bit_index).unwrap();
+        assert_eq!(7, packet.evaluate())
+    }
+}
+
+/// Property tests on generated packets
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use crate::generator::{encode, random_packet};
+    use aoc_utils::Rng;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn decode_inverts_encode(seed in any::<u64>(), size in 1usize..200) {
+            let packet = random_packet(&mut Rng::new(seed), size);
+            let hex = encode(&packet);
+            prop_assert_eq!(&packet, &Packet::decode(&hex).unwrap());
+            prop_assert_eq!(hex.clone(), encode(&Packet::decode(&hex).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Drawing of the probe trajectories
+
+use aoc_utils::{ImageExport, ParseError, Rgb, Solution, Style, Svg};
+
+use crate::{trajectory, Day17, TargetArea};
+
+/// Exports all launches that hit the target
+pub const EXPORT: ImageExport = ImageExport {
+    day: 17,
+    extension: "svg",
+    description: "trajectories of all launches hitting the target area, the highest one in orange",
+    render,
+};
+
+/// Size of the drawing in pixels, the axes are scaled independently to fill it
+const SIZE: f64 = 800.0;
+const MARGIN: f64 = 20.0;
+
+fn render(input: &str) -> Result<Vec<u8>, ParseError> {
+    Ok(draw_trajectories(&Day17::parse(input)?).to_string().into_bytes())
+}
+
+/// Draws the target area and the trajectories of all launches that hit it
+pub fn draw_trajectories(target: &TargetArea) -> Svg {
+    // Same velocities as searched by `solve_2`
+    let trajectories: Vec<Vec<(i32, i32)>> = (0..=target.max.0)
+        .flat_map(|x| (target.min.1..=-target.min.1 - 1).map(move |y| (x, y)))
+        .filter_map(|v| trajectory(v, target))
+        .collect();
+
+    let top = trajectories.iter().flatten().map(|p| p.1).max().unwrap_or(0).max(0);
+    let (scale_x, scale_y) = ((SIZE - 2.0 * MARGIN) / target.max.0.max(1) as f64, (SIZE - 2.0 * MARGIN) / (top - target.min.1).max(1) as f64);
+    let to_pixels = |(x, y): (i32, i32)| (MARGIN + x as f64 * scale_x, MARGIN + (top - y) as f64 * scale_y);
+
+    let mut svg = Svg::new(SIZE, SIZE);
+    svg.rect((0.0, 0.0), (SIZE, SIZE), &Style::fill(Rgb(255, 255, 255)));
+    let (corner, opposite) = (to_pixels((target.min.0, target.max.1)), to_pixels((target.max.0, target.min.1)));
+    svg.rect(corner, (opposite.0 - corner.0, opposite.1 - corner.1), &Style::fill(Rgb(230, 60, 60)).with_opacity(0.4));
+    svg.line(to_pixels((0, 0)), to_pixels((target.max.0, 0)), &Style::stroke(Rgb(120, 120, 120), 1.0));
+
+    let trajectory_style = Style::stroke(Rgb(40, 90, 200), 1.0).with_opacity(0.2);
+    trajectories.iter().for_each(|t| _ = svg.polyline(&t.iter().copied().map(to_pixels).collect::<Vec<_>>(), &trajectory_style));
+    if let Some(highest) = trajectories.iter().max_by_key(|t| t.iter().map(|p| p.1).max()) {
+        svg.polyline(&highest.iter().copied().map(to_pixels).collect::<Vec<_>>(), &Style::stroke(Rgb(240, 140, 30), 2.0));
+    }
+
+    svg
+}
+
+#[cfg(test)]
+mod tests_export {
+    use super::*;
+
+    #[test]
+    fn export() {
+        let svg = draw_trajectories(&crate::parse_input("target area: x=20..30, y=-10..-5").unwrap()).to_string();
+        // Background, target area, launcher line, 112 trajectories and the highest trajectory
+        assert_eq!(2 + 1 + 112 + 1, svg.lines().filter(|l| l.trim_start().starts_with('<') && !l.contains("svg")).count());
+        assert_eq!(113, svg.matches("<polyline").count());
+        // The highest trajectory (6, 9) peaks at y = 45, the top of the drawing
+        assert!(svg.contains("stroke=\"#f08c1e\" stroke-width=\"2\""));
+        let highest = svg.lines().rev().nth(1).unwrap();
+        assert!(highest.starts_with("  <polyline points=\"20,641.82 197.33,517.45 "));
+        assert!(highest.contains(" 729.33,20 "));
+    }
+}
@@ -0,0 +1,226 @@
+//! Day 17: Trick Shot
+#![warn(missing_docs)]
+
+use aoc_utils::{parse_line, LineParser, ParseError, Solution};
+
+pub mod export;
+
+/// Target area of the probe
+pub struct TargetArea {
+    /// Lower bounds (x, y)
+    pub min: (i32, i32),
+    /// Upper bounds (x, y)
+    pub max: (i32, i32),
+}
+
+/// Parses a range like `x=20..30`, fails if the range is empty
+fn parse_range(line: &mut LineParser, axis: &str) -> Result<(i32, i32), ParseError> {
+    line.literal(axis)?;
+    line.literal("=")?;
+    let column = line.column();
+    let from = line.number()?;
+    line.literal("..")?;
+    let to = line.number()?;
+    if from > to {
+        return Err(ParseError::new(line.line(), column, format!("empty range {}..{}", from, to)));
+    }
+
+    Ok((from, to))
+}
+
+/// Parses input (e.g. `target area: x=20..30, y=-10..-5`)
+///
+/// Fails if the target area is not below the launcher, the solutions rely on that.
+pub fn parse_input(input: &str) -> Result<TargetArea, ParseError> {
+    parse_line(input, |line| {
+        line.literal("target area: ")?;
+        let (min_x, max_x) = parse_range(line, "x")?;
+        line.literal(", ")?;
+        let column = line.column();
+        let (min_y, max_y) = parse_range(line, "y")?;
+        if max_y >= 0 {
+            return Err(ParseError::new(line.line(), column, "target area has to be below the launcher (y < 0)"));
+        }
+
+        Ok(TargetArea { min: (min_x, min_y), max: (max_x, max_y) })
+    })
+}
+
+/// Highest y position the probe can reach and still hit a target below the launcher with the given lower y bound
+///
+/// Assumes that the highest launch (y velocity `-min_y - 1`) hits the target with some x velocity, as in all puzzle inputs.
+pub fn solve_1(min_y: i32) -> i32 {
+    min_y * (min_y + 1) / 2
+}
+
+/// Number of initial velocities that hit the target area
+pub fn solve_2(min: (i32, i32), max: (i32, i32)) -> i32 {
+    let range_x = min.0..=max.0;
+    let range_y = min.1..=max.1;
+    let mut count = 0;
+    for x in 0..=max.0 {
+        for y in min.1..=min.1.abs() - 1 {
+            let mut t = 1;
+            loop {
+                let mut xt = 0;
+                let mut xc = x;
+                let mut yt = 0;
+                let mut yc = y;
+                for _ in 0..t {
+                    xt += xc;
+                    yt += yc;
+                    if xc > 0 {
+                        xc -= 1;
+                    }
+                    yc -= 1;
+                }
+
+                if xt > max.0 || yt < min.1 {
+                    break;
+                }
+
+                if range_x.contains(&xt) && range_y.contains(&yt) {
+                    count += 1;
+                    break;
+                }
+
+                t += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Positions of a probe launched from `(0, 0)` until it is in the target area or has passed it, `None` if it misses
+///
+/// The last position is in the target area. Like [`solve_2`], assumes that the target is to the right of and below the launcher.
+pub fn trajectory(mut velocity: (i32, i32), target: &TargetArea) -> Option<Vec<(i32, i32)>> {
+    let mut positions = vec![(0, 0)];
+    let mut position = (0, 0);
+    while position.0 <= target.max.0 && position.1 >= target.min.1 {
+        if position.0 >= target.min.0 && position.1 <= target.max.1 {
+            return Some(positions);
+        }
+
+        position = (position.0 + velocity.0, position.1 + velocity.1);
+        positions.push(position);
+        velocity = (velocity.0 - velocity.0.signum(), velocity.1 - 1);
+    }
+
+    None
+}
+
+/// Solution for day 17
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: i32 = 17;
+    type Input = TargetArea;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(target: &Self::Input) -> Self::Answer1 {
+        solve_1(target.min.1)
+    }
+
+    fn part2(target: &Self::Input) -> Self::Answer2 {
+        solve_2(target.min, target.max)
+    }
+}
+
+aoc_utils::example_tests!(Day17);
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let res = parse_input("target area: x=20..30, y=-10..-5\n").unwrap();
+        assert_eq!((20, -10), res.min);
+        assert_eq!((30, -5), res.max);
+    }
+
+    #[test]
+    fn test_parse_malformed_input() {
+        let err = |input| parse_input(input).err().map(|e| e.to_string());
+        assert_eq!(Some("line 1, column 18: expected '..', found ',30,'".to_string()), err("target area: x=20,30, y=-10..-5"));
+        assert_eq!(Some("line 1, column 16: empty range 30..20".to_string()), err("target area: x=30..20, y=-10..-5"));
+        assert_eq!(Some("line 1, column 24: target area has to be below the launcher (y < 0)".to_string()), err("target area: x=20..30, y=5..10"));
+        assert_eq!(Some("line 1, column 1: input is empty".to_string()), err(""));
+    }
+
+    #[test]
+    fn test_solve_1() {
+        assert_eq!(45, solve_1(-10));
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_solve_2() {
+        let res = solve_2((20, -10), (30, -5));
+        assert_eq!(112, res);
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let target = parse_input("target area: x=20..30, y=-10..-5").unwrap();
+        assert_eq!(Some(vec![(0, 0), (7, 2), (13, 3), (18, 3), (22, 2), (25, 0), (27, -3), (28, -7)]), trajectory((7, 2), &target));
+        assert_eq!(None, trajectory((17, -4), &target));
+        assert_eq!(Some(45), trajectory((6, 9), &target).and_then(|t| t.iter().map(|p| p.1).max()));
+
+        let hits = (0..=30).flat_map(|x| (-10..10).map(move |y| (x, y))).filter(|v| trajectory(*v, &target).is_some()).count();
+        assert_eq!(112, hits);
+    }
+}
+
+/// Property tests against a full simulation of every launch
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Highest y position of the launch if it hits the target
+    fn launch(mut velocity: (i32, i32), min: (i32, i32), max: (i32, i32)) -> Option<i32> {
+        let (mut position, mut highest) = ((0, 0), 0);
+        while position.0 <= max.0 && position.1 >= min.1 {
+            if position.0 >= min.0 && position.1 <= max.1 {
+                return Some(highest);
+            }
+
+            position = (position.0 + velocity.0, position.1 + velocity.1);
+            highest = highest.max(position.1);
+            velocity = (velocity.0 - velocity.0.signum(), velocity.1 - 1);
+        }
+
+        None
+    }
+
+    /// Targets below the launcher that the highest launch can hit (see [`solve_1`])
+    fn target() -> impl Strategy<Value = ((i32, i32), (i32, i32))> {
+        (1i32..40, 0i32..20, -30i32..-1, 0i32..20)
+            .prop_map(|(min_x, width, min_y, height)| ((min_x, min_y), (min_x + width, (min_y + height).min(-1))))
+            .prop_filter("highest launch misses", |(min, max)| (0..=max.0).any(|x| launch((x, -min.1 - 1), *min, *max).is_some()))
+    }
+
+    proptest! {
+        #[test]
+        fn solutions_match_simulation((min, max) in target()) {
+            // Launches beyond these velocities overshoot the target in the first step or after falling back to y = 0
+            let heights: Vec<i32> = (0..=max.0).flat_map(|x| (min.1..=-min.1).map(move |y| (x, y))).filter_map(|v| launch(v, min, max)).collect();
+            prop_assert_eq!(heights.len() as i32, solve_2(min, max));
+            prop_assert_eq!(heights.iter().max().copied(), Some(solve_1(min.1)));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Random inputs for day 18
+
+use aoc_utils::{InputGenerator, Rng};
+
+/// Generates homework assignments, `size` is the number of snailfish numbers
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 18,
+    size: "number of snailfish numbers",
+    default_size: 100,
+    generate,
+};
+
+/// Generates `size` reduced snailfish numbers: pairs are nested at most 4 deep and all regular numbers are below 10
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size).map(|_| pair(rng, 1)).collect::<Vec<_>>().join("\n")
+}
+
+/// Random pair inside `depth - 1` other pairs
+fn pair(rng: &mut Rng, depth: usize) -> String {
+    let element = |rng: &mut Rng| {
+        if depth < 4 && rng.chance(0.6) {
+            pair(rng, depth + 1)
+        } else {
+            rng.below(10).to_string()
+        }
+    };
+
+    let left = element(rng);
+    format!("[{},{}]", left, element(rng))
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+    use crate::{process, process_input};
+
+    #[test]
+    fn test_generate() {
+        let input = GENERATOR.generate(18, Some(20));
+        assert_eq!(20, input.lines().count());
+        assert!(input.lines().all(|number| process(number) == number));
+        assert!(input.lines().any(|number| number.starts_with("[[[[")));
+        process_input(&input);
+    }
+}
@@ -0,0 +1,128 @@
+//! Random inputs for day 19 with known scanner positions
+
+use std::collections::HashSet;
+
+use aoc_utils::{InputGenerator, Rng, Rotation, ROTATIONS};
+
+use crate::{distance_key, v3, Vector3d};
+
+/// Generates scanner reports, `size` is the number of scanners
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 19,
+    size: "number of scanners",
+    default_size: 30,
+    generate,
+};
+
+/// Range in which a scanner detects beacons (in each direction)
+const RANGE: i32 = 1000;
+
+/// Generated scanner reports with the ground truth they were generated from
+pub struct GeneratedScanners {
+    /// Puzzle input
+    pub input: String,
+    /// Positions of the scanners relative to scanner 0
+    pub positions: Vec<Vector3d>,
+    /// All beacons relative to scanner 0
+    pub beacons: HashSet<Vector3d>,
+}
+
+impl GeneratedScanners {
+    /// Largest Manhattan distance between any two scanners (answer of part 2)
+    pub fn max_manhattan_distance(&self) -> i32 {
+        self.positions.iter().flat_map(|p1| self.positions.iter().map(|p2| p1.manhattan_distance(p2))).max().unwrap_or_default()
+    }
+}
+
+/// Generates the reports of `size` scanners (at least 1)
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_scanners(rng, size).input
+}
+
+/// Generates the reports of `size` scanners (at least 1) together with their positions and all beacons
+///
+/// Scanner 0 is at the origin and not rotated. Every further scanner is placed near a random earlier
+/// scanner and shares at least 12 beacons with it, whose pairwise distances are all different, so that
+/// every scanner can be aligned. Each scanner reports all beacons in its range in one of the 24 orientations.
+pub fn generate_scanners(rng: &mut Rng, size: usize) -> GeneratedScanners {
+    let origin = v3![0, 0, 0];
+    let mut positions = vec![origin];
+    let mut beacons = Vec::new();
+    let mut known = HashSet::new();
+    let one = v3![RANGE, RANGE, RANGE];
+    let count = rng.range(10..=15) as usize;
+    add_beacons(rng, &mut beacons, &mut known, origin - one, origin + one, count);
+
+    for _ in 1..size.max(1) {
+        let parent = *rng.choose(&positions);
+        let mut offset = || rng.range(-1100..=1100) as i32;
+        let position = parent + v3![offset(), offset(), offset()];
+
+        // Shared beacons lie in the intersection of both ranges
+        let low = v3![parent.x.max(position.x) - RANGE, parent.y.max(position.y) - RANGE, parent.z.max(position.z) - RANGE];
+        let high = v3![parent.x.min(position.x) + RANGE, parent.y.min(position.y) + RANGE, parent.z.min(position.z) + RANGE];
+        let shared = loop {
+            let count = rng.range(12..=14) as usize;
+            let mut candidates = Vec::new();
+            add_beacons(rng, &mut candidates, &mut known.clone(), low, high, count);
+            let lengths: HashSet<u64> = candidates.iter().enumerate().flat_map(|(i, b1)| candidates[i + 1..].iter().map(move |b2| distance_key(*b2 - *b1))).collect();
+            if lengths.len() == count * (count - 1) / 2 {
+                break candidates;
+            }
+        };
+
+        known.extend(shared.iter().cloned());
+        beacons.extend(shared);
+        let count = rng.range(8..=14) as usize;
+        add_beacons(rng, &mut beacons, &mut known, position - one, position + one, count);
+        positions.push(position);
+    }
+
+    let mut input = Vec::new();
+    for (scanner, position) in positions.iter().enumerate() {
+        let rotation = if scanner == 0 { Rotation::IDENTITY } else { *rng.choose(&ROTATIONS) };
+        let mut detected: Vec<Vector3d> = beacons.iter().map(|b| *b - *position).filter(|b| b.x.abs() <= RANGE && b.y.abs() <= RANGE && b.z.abs() <= RANGE).collect();
+        rng.shuffle(&mut detected);
+
+        input.push(format!("--- scanner {} ---", scanner));
+        input.extend(detected.into_iter().map(|b| rotation.apply(b).to_string()));
+        input.push(String::new());
+    }
+
+    input.pop();
+    GeneratedScanners {
+        input: input.join("\n"),
+        positions,
+        beacons: known,
+    }
+}
+
+/// Adds `count` new beacons between `low` and `high` (inclusive)
+fn add_beacons(rng: &mut Rng, beacons: &mut Vec<Vector3d>, known: &mut HashSet<Vector3d>, low: Vector3d, high: Vector3d, count: usize) {
+    let mut added = 0;
+    while added < count {
+        let mut coordinate = |low: i32, high: i32| rng.range(low as i64..=high as i64) as i32;
+        let beacon = v3![coordinate(low.x, high.x), coordinate(low.y, high.y), coordinate(low.z, high.z)];
+        if known.insert(beacon) {
+            beacons.push(beacon);
+            added += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+    use crate::Day19;
+    use aoc_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        let generated = generate_scanners(&mut Rng::new(19), 6);
+        assert_eq!(6, generated.input.lines().filter(|l| l.starts_with("---")).count());
+
+        let scanners = Day19::parse(&generated.input).unwrap();
+        assert_eq!(generated.beacons, scanners.aligned().beacons);
+        assert_eq!(generated.max_manhattan_distance(), Day19::part2(&scanners));
+    }
+}
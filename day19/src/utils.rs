@@ -1,8 +1,8 @@
-use std::{
-    collections::HashSet,
-    ops::{Add, Sub},
-};
+use std::collections::HashSet;
 
+use aoc_utils::{Point3, Rotation, ROTATIONS};
+
+/// Creates a [`Vector3d`] from its coordinates
 #[macro_export]
 macro_rules! v3 {
     ($x:expr, $y:expr, $z:expr) => {
@@ -10,104 +10,35 @@ macro_rules! v3 {
     };
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
-pub struct Vector3d {
-    pub x: i32,
-    pub y: i32,
-    pub z: i32,
-}
-
-impl From<&str> for Vector3d {
-    fn from(vs: &str) -> Self {
-        let mut vsi = vs.split(',');
-        v3![vsi.next().unwrap().parse().unwrap(), vsi.next().unwrap().parse().unwrap(), vsi.next().unwrap().parse().unwrap()]
-    }
-}
-
-impl Sub for Vector3d {
-    type Output = Vector3d;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
-    }
-}
-
-impl Add for Vector3d {
-    type Output = Vector3d;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-        }
-    }
-}
-
-impl Vector3d {
-    pub fn length(&self) -> u64 {
-        (((self.x * self.x + self.y * self.y + self.z * self.z) as f64).sqrt() * 10f64.powi(5)).trunc() as u64
-    }
+/// Position (of a beacon or scanner) or movement in 3D space
+pub type Vector3d = Point3<i32>;
 
-    pub fn manhattan_distance(&self, other: &Self) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
-    }
+/// Euclidean length in units of 10^-5, truncated so that it can be compared and hashed
+pub fn distance_key(v: Vector3d) -> u64 {
+    (v.length() * 10f64.powi(5)).trunc() as u64
 }
 
-pub type Rotator = fn(Vector3d) -> Vector3d;
-
-pub const ROTATORS: [Rotator; 24] = [
-    // View from 0/0/1
-    |v| v3![v.x, v.y, v.z],
-    |v| v3![v.y, -v.x, v.z],
-    |v| v3![-v.x, -v.y, v.z],
-    |v| v3![-v.y, v.x, v.z],
-    // View from 0/0/-1
-    |v| v3![-v.x, v.y, -v.z],
-    |v| v3![v.y, v.x, -v.z],
-    |v| v3![v.x, -v.y, -v.z],
-    |v| v3![-v.y, -v.x, -v.z],
-    // View from 1/0/0
-    |v| v3![-v.z, v.y, v.x],
-    |v| v3![v.y, v.z, v.x],
-    |v| v3![v.z, -v.y, v.x],
-    |v| v3![-v.y, -v.z, v.x],
-    // View from -1/0/0
-    |v| v3![v.z, v.y, -v.x],
-    |v| v3![v.y, -v.z, -v.x],
-    |v| v3![-v.z, -v.y, -v.x],
-    |v| v3![-v.y, v.z, -v.x],
-    // View from 0/1/0
-    |v| v3![-v.z, -v.x, v.y],
-    |v| v3![-v.x, v.z, v.y],
-    |v| v3![v.z, v.x, v.y],
-    |v| v3![v.x, -v.z, v.y],
-    // View from 0/-1/0
-    |v| v3![v.z, -v.x, -v.y],
-    |v| v3![-v.x, -v.z, -v.y],
-    |v| v3![-v.z, v.x, -v.y],
-    |v| v3![v.x, v.z, -v.y],
-];
-
+/// Transformation from the coordinates of one scanner into those of another: rotation followed by movement
+#[derive(Clone, Default)]
 pub struct Translation {
+    /// Subtracted after rotating
     pub movement: Vector3d,
-    pub rotator_fn: Rotator,
+    /// Index of the rotation in [`ROTATIONS`]
     pub rotator_ix: usize,
 }
 
 impl Translation {
+    /// Creates a translation with the rotation `ROTATIONS[rotator_ix]`
     pub fn new(movement: Vector3d, rotator_ix: usize) -> Self {
-        Self {
-            movement,
-            rotator_ix,
-            rotator_fn: ROTATORS[rotator_ix],
-        }
+        Self { movement, rotator_ix }
+    }
+
+    /// Rotation of the translation
+    pub fn rotation(&self) -> Rotation {
+        ROTATIONS[self.rotator_ix]
     }
 
+    /// Translates all vectors of a set
     pub fn translate_set(&self, s: &HashSet<Vector3d>) -> HashSet<Vector3d> {
         let mut result = HashSet::<Vector3d>::with_capacity(s.len());
         for sb in s.iter().cloned() {
@@ -117,43 +48,32 @@ impl Translation {
         result
     }
 
+    /// Rotates and moves a vector
     pub fn translate(&self, v: Vector3d) -> Vector3d {
         self.rotate(v) - self.movement
     }
 
+    /// Only rotates a vector (e.g. a movement)
     pub fn rotate(&self, v: Vector3d) -> Vector3d {
-        (self.rotator_fn)(v)
-    }
-}
-
-impl Clone for Translation {
-    fn clone(&self) -> Self {
-        Self {
-            movement: self.movement,
-            rotator_fn: ROTATORS[self.rotator_ix],
-            rotator_ix: self.rotator_ix,
-        }
-    }
-}
-
-impl Default for Translation {
-    fn default() -> Self {
-        Self {
-            movement: Default::default(),
-            rotator_fn: ROTATORS[0],
-            rotator_ix: Default::default(),
-        }
+        self.rotation().apply(v)
     }
 }
 
+/// Beacons detected by a scanner and, once aligned, its position relative to scanner 0
+#[derive(Clone)]
 pub struct ScannerData {
+    /// Beacons relative to the scanner
     pub beacons: HashSet<Vector3d>,
+    /// Distances between all pairs of beacons (see [`distance_key`]), independent of the orientation of the scanner
     pub distances: HashSet<u64>,
+    /// Position of the scanner relative to scanner 0
     pub movement_to_zero: Vector3d,
+    /// Translations that convert coordinates of this scanner into coordinates of scanner 0
     pub translations_to_zero: Vec<Translation>,
 }
 
 impl ScannerData {
+    /// Creates the data of a not yet aligned scanner
     pub fn new(beacons: Vec<Vector3d>) -> Self {
         ScannerData {
             beacons: HashSet::from_iter(beacons.iter().cloned()),
@@ -168,7 +88,7 @@ impl ScannerData {
         let mut distances = HashSet::<_>::with_capacity(len * (len - 1) / 2);
         for i in 0..len - 1 {
             for j in i + 1..len {
-                distances.insert((beacons[j] - beacons[i]).length());
+                distances.insert(distance_key(beacons[j] - beacons[i]));
             }
         }
 
@@ -179,13 +99,14 @@ impl ScannerData {
 #[cfg(test)]
 mod tests_utils {
     use super::*;
-    use std::collections::HashSet;
+    use std::str::FromStr;
 
     #[test]
     fn test_from_str() {
-        assert_eq!(v3![1, 2, 3], "1,2,3".into());
-        assert_eq!(v3![10, 20, 30], "10,20,30".into());
-        assert_eq!(v3![10, 20, 30], Vector3d::from("10,20,30"));
+        assert_eq!(Ok(v3![1, 2, 3]), "1,2,3".parse());
+        assert_eq!(Ok(v3![10, 20, 30]), "10,20,30".parse());
+        assert_eq!(Ok(v3![-10, 20, -30]), Vector3d::from_str("-10,20,-30"));
+        assert_eq!("line 1, column 5: expected number, found end of line", Vector3d::from_str("1,2,").unwrap_err().to_string());
     }
 
     #[test]
@@ -205,8 +126,8 @@ mod tests_utils {
         let mut rotated = Vec::<HashSet<Vector3d>>::with_capacity(originals.len());
         for o in originals {
             let mut hs = HashSet::<Vector3d>::with_capacity(24);
-            for r in ROTATORS {
-                hs.insert(r(o));
+            for r in ROTATIONS {
+                hs.insert(r.apply(o));
             }
 
             rotated.push(hs);
@@ -251,13 +172,13 @@ mod tests_utils {
 
     #[test]
     fn test_length() {
-        assert_eq!(538516, v3![2, 3, 4].length())
+        assert_eq!(538516, distance_key(v3![2, 3, 4]))
     }
 
     #[test]
     fn test_get_distances() {
-        let result = ScannerData::get_distances(&vec![v3![404, -588, -901], v3![528, -643, 409]]);
-        assert_eq!(result.len(), 2 * 1 / 2);
+        let result = ScannerData::get_distances(&[v3![404, -588, -901], v3![528, -643, 409]]);
+        assert_eq!(result.len(), 1);
         assert!(result.contains(&131700455));
     }
 
@@ -0,0 +1,67 @@
+//! Animation of the growing image
+
+use aoc_utils::{Animation, Frame, Palette, ParseError, Rgb, Solution, Visualization};
+
+use crate::{Day20, ImageProcessor};
+
+/// Shows the 50 enhancements of star 2
+pub const VISUALIZATION: Visualization = Visualization {
+    day: 20,
+    description: "image growing with each enhancement",
+    create,
+};
+
+fn create(input: &str) -> Result<Box<dyn Animation>, ParseError> {
+    Ok(Box::new(Enhancing::new(Day20::parse(input)?, 50)))
+}
+
+/// Image after each enhancement, with two rows/columns of the outer area around it
+pub struct Enhancing {
+    processor: ImageProcessor,
+    enhancements: usize,
+    done: usize,
+}
+
+impl Enhancing {
+    /// Animation of `enhancements` enhancements of the image
+    pub fn new(processor: ImageProcessor, enhancements: usize) -> Self {
+        Enhancing { processor, enhancements, done: 0 }
+    }
+}
+
+impl Animation for Enhancing {
+    fn frame(&self) -> Frame {
+        let title = format!("Enhancement {} of {}, {} pixels lit", self.done, self.enhancements, self.processor.count_lit());
+        Frame::new(title, &self.processor.render(2))
+    }
+
+    fn step(&mut self) -> bool {
+        if self.done == self.enhancements {
+            return false;
+        }
+
+        self.processor.enhance();
+        self.done += 1;
+        true
+    }
+
+    fn palette(&self) -> Palette {
+        &[('#', Rgb(255, 230, 120)), ('.', Rgb(60, 60, 90))]
+    }
+}
+
+#[cfg(test)]
+mod tests_animation {
+    use super::*;
+
+    #[test]
+    fn enhance() {
+        let mut animation = Enhancing::new(Day20::parse(include_str!("../examples/example.txt")).unwrap(), 2);
+        assert_eq!(9, animation.frame().lines.len());
+        assert!(animation.step() && animation.step() && !animation.step());
+
+        let frame = animation.frame();
+        assert_eq!("Enhancement 2 of 2, 35 pixels lit", frame.title);
+        assert_eq!(13, frame.lines.len());
+    }
+}
@@ -0,0 +1,38 @@
+//! Picture of the enhanced image
+
+use aoc_utils::{encode_pbm, ImageExport, ParseError, Solution};
+
+use crate::{Day20, ImageProcessor};
+
+/// Exports the image of star 2
+pub const EXPORT: ImageExport = ImageExport {
+    day: 20,
+    extension: "pbm",
+    description: "image after 50 enhancements, lit pixels in black",
+    render,
+};
+
+fn render(input: &str) -> Result<Vec<u8>, ParseError> {
+    Ok(render_enhanced(Day20::parse(input)?, 50))
+}
+
+/// PBM of the image after the given number of enhancements (without the outer area)
+pub fn render_enhanced(mut processor: ImageProcessor, enhancements: usize) -> Vec<u8> {
+    (0..enhancements).for_each(|_| processor.enhance());
+    encode_pbm(processor.image())
+}
+
+#[cfg(test)]
+mod tests_export {
+    use super::*;
+
+    #[test]
+    fn export() {
+        let image = render_enhanced(Day20::parse(include_str!("../examples/example.txt")).unwrap(), 0);
+        // #..#. / #.... / ##..# / ..#.. / ..###
+        assert_eq!(b"P4\n5 5\n\x90\x80\xc8\x20\x38".as_slice(), image);
+
+        let image = render_enhanced(Day20::parse(include_str!("../examples/example.txt")).unwrap(), 2);
+        assert!(image.starts_with(b"P4\n9 9\n"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Day 20: Trench Map
+#![warn(missing_docs)]
+
+use aoc_utils::{normalize_input, parse_line, Grid, ParseError, Solution};
+use bitvec::prelude::*;
+
+pub mod animation;
+pub mod export;
+
+/// Image enhancement algorithm and the (infinite) image it is applied to
+#[derive(Clone, Debug)]
+pub struct ImageProcessor {
+    enhancement: BitVec,
+    image: Grid<bool>,
+    outer: bool,
+}
+
+/// Parses the enhancement algorithm and the input image
+pub fn parse_input(input: &str) -> Result<ImageProcessor, ParseError> {
+    // Sections are separated by an empty line, which only works with `\n` line endings
+    let input = normalize_input(input);
+    let mut sections = input.splitn(2, "\n\n");
+
+    let enhancement_str = sections.next().unwrap_or_default();
+    let enhancement = parse_line(enhancement_str, |line| {
+        let pixels = line.take_while("'#' or '.'", |c| c == '#' || c == '.')?;
+        if pixels.len() != 1 << 9 {
+            return Err(ParseError::new(line.line(), 1, format!("expected {} pixels, found {}", 1 << 9, pixels.len())));
+        }
+
+        Ok(pixels.chars().map(|c| c == '#').collect::<BitVec>())
+    })?;
+
+    let image_str = sections
+        .next()
+        .ok_or_else(|| ParseError::new(input.lines().count() + 1, 1, "expected an empty line followed by the image"))?;
+    let image = Grid::parse(image_str, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .map_err(|e| {
+        // Rows of the image are numbered from the line after the empty line
+        let mut err = ParseError::from(e);
+        err.line += enhancement_str.lines().count() + 1;
+        err
+    })?;
+
+    Ok(ImageProcessor { enhancement, image, outer: false })
+}
+
+impl ImageProcessor {
+    /// Renders the image with `padding` rows/columns of the infinite outer area around it (`#` = lit)
+    pub fn render(&self, padding: usize) -> String {
+        let offset = padding as isize;
+        let padded = Grid::from_fn(self.image.width() + 2 * padding, self.image.height() + 2 * padding, |(x, y)| {
+            let lit = *self.image.get_signed(x as isize - offset, y as isize - offset).unwrap_or(&self.outer);
+            if lit { '#' } else { '.' }
+        });
+
+        padded.to_string()
+    }
+
+    /// Image pixels (`true` = lit); pixels outside of the grid have the value [`ImageProcessor::outer`]
+    pub fn image(&self) -> &Grid<bool> {
+        &self.image
+    }
+
+    /// Value of all pixels of the infinite area outside of [`ImageProcessor::image`]
+    pub fn outer(&self) -> bool {
+        self.outer
+    }
+
+    /// Applies the enhancement algorithm once, the image grows by one pixel on each side
+    pub fn enhance(&mut self) {
+        let new_image = Grid::from_fn(self.image.width() + 2, self.image.height() + 2, |(col, row)| {
+            let mut ix = 0usize;
+            for inner_row in 0..3 {
+                for inner_col in 0..3 {
+                    ix <<= 1;
+                    let x = (col + inner_col) as isize - 2;
+                    let y = (row + inner_row) as isize - 2;
+                    ix |= if *self.image.get_signed(x, y).unwrap_or(&self.outer) { 1 } else { 0 };
+                }
+            }
+
+            self.enhancement[ix]
+        });
+
+        let new_outer = if self.outer {
+            self.enhancement[(1 << 9) - 1]
+        } else {
+            self.enhancement[0]
+        };
+
+        self.image = new_image;
+        self.outer = new_outer;
+    }
+
+    /// Number of lit pixels in the image (ignoring the outer area)
+    pub fn count_lit(&self) -> u32 {
+        self.image.iter().filter(|lit| **lit).count() as u32
+    }
+}
+
+/// Solution for day 20
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: i32 = 20;
+    type Input = ImageProcessor;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        let mut image = input.clone();
+        for _ in 0..2 {
+            image.enhance();
+        }
+
+        image.count_lit()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        let mut image = input.clone();
+        for _ in 0..50 {
+            image.enhance();
+        }
+
+        image.count_lit()
+    }
+}
+
+aoc_utils::example_tests!(Day20);
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    const TEST_DATA: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    #[test]
+    fn test_parse() {
+        let input = parse_input(TEST_DATA).unwrap();
+
+        assert_eq!(5 * 5, input.image.len());
+        assert_eq!(512, input.enhancement.len());
+        assert!(input.enhancement[2]);
+        assert!(!input.enhancement[1]);
+        assert!(input.image[(0, 0)]);
+        assert!(!input.image[(1, 0)]);
+    }
+
+    #[test]
+    fn test_parse_line_endings() {
+        for input in aoc_utils::line_ending_variants(TEST_DATA) {
+            let input = parse_input(&input).unwrap();
+            assert_eq!(512, input.enhancement.len());
+            assert_eq!((5, 5), (input.image.width(), input.image.height()));
+            assert_eq!(10, input.count_lit());
+        }
+    }
+
+    #[test]
+    fn test_parse_malformed_input() {
+        let err = parse_input(&TEST_DATA.replace("\n\n", "\n")).unwrap_err();
+        assert_eq!("line 2, column 1: expected 512 pixels, found 5", err.to_string());
+        let err = parse_input(TEST_DATA.split("\n\n").next().unwrap()).unwrap_err();
+        assert_eq!("line 2, column 1: expected an empty line followed by the image", err.to_string());
+        let err = parse_input(&TEST_DATA[1..]).unwrap_err();
+        assert_eq!("line 1, column 1: expected 512 pixels, found 511", err.to_string());
+        let err = parse_input(&TEST_DATA.replace("##..#\n", "##.x#\n")).unwrap_err();
+        assert_eq!("line 5, column 4: invalid cell 'x' at row 3, column 4", err.to_string());
+    }
+
+    #[test]
+    fn test_enhance_1() {
+        let mut input = parse_input(TEST_DATA).unwrap();
+        input.enhance();
+        input.enhance();
+
+        assert_eq!(35, input.count_lit());
+    }
+
+    #[test]
+    fn test_render() {
+        let mut input = parse_input(TEST_DATA).unwrap();
+        assert_eq!("#..#.\n#....\n##..#\n..#..\n..###", input.render(0));
+
+        input.enhance();
+        assert_eq!(
+            ".........\n..##.##..\n.#..#.#..\n.##.#..#.\n.####..#.\n..#..##..\n...##..#.\n....#.#..\n.........",
+            input.render(1)
+        );
+    }
+}
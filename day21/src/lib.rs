@@ -0,0 +1,166 @@
+//! Day 21: Dirac Dice
+#![warn(missing_docs)]
+
+use std::collections::{HashMap, hash_map::Entry::{Occupied, Vacant}};
+
+use aoc_utils::{input_lines, parse_lines, ParseError, Solution};
+
+/// Parses input (starting positions of both players, 1 to 10)
+pub fn parse_input(input: &str) -> Result<[u8; 2], ParseError> {
+    let mut player = 0;
+    let positions = parse_lines(input, |l| {
+        player += 1;
+        l.literal("Player ")?;
+        let column = l.column();
+        if l.number::<u8>()? != player {
+            return Err(ParseError::new(l.line(), column, format!("expected player {}", player)));
+        }
+
+        l.literal(" starting position: ")?;
+        let column = l.column();
+        match l.number()? {
+            position @ 1..=10 => Ok(position),
+            position => Err(ParseError::new(l.line(), column, format!("invalid starting position {}, expected 1 to 10", position))),
+        }
+    })?;
+
+    positions.try_into().map_err(|positions: Vec<u8>| match input_lines(input).nth(2) {
+        Some(line) => line.error("expected two players"),
+        None => ParseError::new(positions.len() + 1, 1, "expected two players"),
+    })
+}
+
+/// Plays with the deterministic die, starting positions are 0-based
+///
+/// Returns the score of the losing player and the number of die rolls.
+// Trivial solution; could be enhanced but isn't worth it as the real challenge is second star.
+pub fn solve_1(mut start_1: u32, mut start_2: u32) -> (u32, u32) {
+    let mut dice = 1u32;
+    let mut p1 = 0u32;
+    let mut p2 = 0u32;
+
+    loop {
+        start_1 = (start_1 + dice + dice + 1 + dice + 2) % 10;
+        dice += 3;
+        p1 += start_1 + 1;
+        if p1 >= 1000 {
+            return (p2, dice - 1);
+        }
+
+        start_2 = (start_2 + dice + dice + 1 + dice + 2) % 10;
+        dice += 3;
+        p2 += start_2 + 1;
+        if p2 >= 1000 {
+            return (p1, dice - 1);
+        }
+    }
+}
+
+/// Calculates the number of dice combinations to reach a certain dice sum.
+pub fn number_of_possibilities() -> HashMap<u8, u64> {
+    let mut possiblities_per_sum = HashMap::<u8, u64>::with_capacity(7);
+    for d1 in 1..=3 {
+        for d2 in 1..=3 {
+            for d3 in 1..=3 {
+                match possiblities_per_sum.entry(d1 + d2 + d3) {
+                    Occupied(mut e) => { e.insert(e.get() + 1); },
+                    Vacant(e) => { e.insert(1); }
+                };
+            }
+        }
+    }
+
+    possiblities_per_sum
+}
+
+/// Plays all turns with the Dirac die from the given state and adds the universes in which each player wins
+///
+/// `possibilities` is the number of universes with the given state, `pos` are the 1-based positions.
+pub fn roll(possiblities_per_sum: &HashMap<u8, u64>, possibilities: u64, player: usize, pos: [u8; 2], points: [u8; 2], universes: &mut [u64; 2]) {
+    for dice_sum in 3..=9 {
+        let mut pos = pos;
+        let mut points = points;
+        let mut possibilities = possibilities;
+        pos[player] = (pos[player] - 1 + dice_sum) % 10 + 1;
+        points[player] += pos[player];
+        possibilities *= possiblities_per_sum[&dice_sum];
+        if points[player] >= 21 {
+            universes[player] += possibilities;
+        } else {
+            roll(possiblities_per_sum, possibilities, (player + 1) % 2, pos, points, universes);
+        }
+    }
+}
+
+/// Solution for day 21
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: i32 = 21;
+    type Input = [u8; 2];
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(start: &Self::Input) -> Self::Answer1 {
+        let result = solve_1(start[0] as u32 - 1, start[1] as u32 - 1);
+        result.0 * result.1
+    }
+
+    fn part2(start: &Self::Input) -> Self::Answer2 {
+        let points = [0u8; 2];
+        let possibilities = 1u64;
+        let mut universes = [0u64; 2];
+        roll(&number_of_possibilities(), possibilities, 0, *start, points, &mut universes);
+        if universes[0] > universes[1] { universes[0] } else { universes[1] }
+    }
+}
+
+aoc_utils::example_tests!(Day21);
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(Ok([4, 8]), parse_input("Player 1 starting position: 4\nPlayer 2 starting position: 8\n"));
+    }
+
+    #[test]
+    fn test_parse_malformed_input() {
+        let err = |input| parse_input(input).unwrap_err().to_string();
+        assert_eq!("line 2, column 29: invalid starting position 11, expected 1 to 10", err("Player 1 starting position: 4\nPlayer 2 starting position: 11"));
+        assert_eq!("line 2, column 8: expected player 2", err("Player 1 starting position: 4\nPlayer 1 starting position: 8"));
+        assert_eq!("line 2, column 1: expected two players", err("Player 1 starting position: 4"));
+        assert_eq!("line 2, column 29: expected number, found 'x'", err("Player 1 starting position: 4\nPlayer 2 starting position: x"));
+    }
+
+    #[test]
+    fn test_1() {
+        let result = solve_1(4 - 1, 8 - 1);
+        assert_eq!(739785, result.0 * result.1);
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_2() {
+        let pos = [4u8, 8];
+        let points = [0u8; 2];
+        let possibilities = 1u64;
+        let mut universes = [0u64; 2];
+        roll(&number_of_possibilities(), possibilities, 0, pos, points, &mut universes);
+
+        assert_eq!(444356092776315, universes[0]);
+        assert_eq!(341960390180808, universes[1]);
+    }
+}
@@ -1,113 +1,66 @@
-use std::cmp::{max, min};
+//! Axis-aligned cuboids of reactor cubes
+//!
+//! Superseded by [`aoc_utils::Cuboid`] (see [`crate::Cube`]), kept so that code using `day22::cubes` keeps compiling.
+//! Both types convert into each other with `From`.
+#![allow(deprecated)]
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
-pub struct Point3d {
-    pub x: i32,
-    pub y: i32,
-    pub z: i32,
-}
+use aoc_utils::{Cuboid, Point3};
 
+/// Position of a cube in the reactor
+pub type Point3d = Point3<i32>;
+
+/// Cuboid of cubes between two corners (both inclusive)
+#[deprecated(note = "use `day22::Cube` (an `aoc_utils::Cuboid<i32>`) instead")]
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct Cube {
+    /// Corner with the smallest coordinates
     pub corner1: Point3d,
+    /// Corner with the largest coordinates
     pub corner2: Point3d,
 }
 
 impl Cube {
+    /// Creates a cuboid from two opposite corners in any order
     pub fn new(x1: i32, y1: i32, z1: i32, x2: i32, y2: i32, z2: i32) -> Self {
-        Self {
-            corner1: Point3d {
-                x: min(x1, x2),
-                y: min(y1, y2),
-                z: min(z1, z2),
-            },
-            corner2: Point3d {
-                x: max(x1, x2),
-                y: max(y1, y2),
-                z: max(z1, z2),
-            },
-        }
+        Cuboid::new(Point3::new(x1, y1, z1), Point3::new(x2, y2, z2)).into()
     }
 
+    /// Number of cubes in the cuboid
     pub fn volume(&self) -> i64 {
-        (self.corner2.x - self.corner1.x + 1) as i64 * (self.corner2.y - self.corner1.y + 1) as i64 * (self.corner2.z - self.corner1.z + 1) as i64
+        Cuboid::from(*self).volume()
     }
 
+    /// Cuboid that is part of both cuboids, if any
     pub fn intersection(&self, other: &Cube) -> Option<Cube> {
-        if (self.corner1.x < other.corner1.x && self.corner2.x < other.corner1.x)
-            || (self.corner1.x > other.corner2.x && self.corner2.x > other.corner2.x)
-            || (self.corner1.y < other.corner1.y && self.corner2.y < other.corner1.y)
-            || (self.corner1.y > other.corner2.y && self.corner2.y > other.corner2.y)
-            || (self.corner1.z < other.corner1.z && self.corner2.z < other.corner1.z)
-            || (self.corner1.z > other.corner2.z && self.corner2.z > other.corner2.z)
-        {
-            return None;
-        }
-
-        Some(Cube::new(
-            max(self.corner1.x, other.corner1.x),
-            max(self.corner1.y, other.corner1.y),
-            max(self.corner1.z, other.corner1.z),
-            min(self.corner2.x, other.corner2.x),
-            min(self.corner2.y, other.corner2.y),
-            min(self.corner2.z, other.corner2.z),
-        ))
+        Cuboid::from(*self).intersection(&Cuboid::from(*other)).map(Cube::from)
     }
 
+    /// Whether the other cuboid lies completely inside this one
     pub fn encloses(&self, other: &Cube) -> bool {
-        self.corner1.x <= other.corner1.x
-            && self.corner1.y <= other.corner1.y
-            && self.corner1.z <= other.corner1.z
-            && self.corner2.x >= other.corner2.x
-            && self.corner2.y >= other.corner2.y
-            && self.corner2.z >= other.corner2.z
+        Cuboid::from(*self).encloses(&Cuboid::from(*other))
     }
 
+    /// Removes the other cuboid from this one, the remaining region is returned as non-overlapping cuboids
     pub fn cut(&self, other: &Cube) -> Vec<Cube> {
-        let mut result = Vec::new();
-
-        if other.encloses(self) {
-            // if cut region encloses, the result is empty
-            return result;
-        }
-
-        if self.intersection(other).is_none() {
-            // if cut region does not intersect, original cuboid is result
-            result.push(*self);
-            return result;
-        }
-
-        // top
-        if let Some(i) = self.intersection(&Cube::new(i32::MIN, other.corner2.y + 1, i32::MIN, i32::MAX, i32::MAX, i32::MAX)) {
-            result.push(i);
-        }
-
-        // bottom
-        if let Some(i) = self.intersection(&Cube::new(i32::MIN, i32::MIN, i32::MIN, i32::MAX, other.corner1.y - 1, i32::MAX)) {
-            result.push(i);
-        }
-
-        // side 1
-        if let Some(i) = self.intersection(&Cube::new(i32::MIN, other.corner1.y, i32::MIN, other.corner1.x - 1, other.corner2.y, i32::MAX)) {
-            result.push(i);
-        }
-
-        // side 2
-        if let Some(i) = self.intersection(&Cube::new(other.corner2.x + 1, other.corner1.y, i32::MIN, i32::MAX, other.corner2.y, i32::MAX)) {
-            result.push(i);
-        }
+        Cuboid::from(*self).cut(&Cuboid::from(*other)).into_iter().map(Cube::from).collect()
+    }
+}
 
-        // side 3
-        if let Some(i) = self.intersection(&Cube::new(other.corner1.x, other.corner1.y, i32::MIN, other.corner2.x, other.corner2.y, other.corner1.z - 1)) {
-            result.push(i);
+impl From<Cuboid<i32>> for Cube {
+    fn from(cuboid: Cuboid<i32>) -> Self {
+        Cube {
+            corner1: cuboid.min,
+            corner2: cuboid.max,
         }
+    }
+}
 
-        // side 4
-        if let Some(i) = self.intersection(&Cube::new(other.corner1.x, other.corner1.y, other.corner2.z + 1, other.corner2.x, other.corner2.y, i32::MAX)) {
-            result.push(i);
+impl From<Cube> for Cuboid<i32> {
+    fn from(cube: Cube) -> Self {
+        Cuboid {
+            min: cube.corner1,
+            max: cube.corner2,
         }
-
-        result
     }
 }
 
@@ -117,117 +70,18 @@ mod tests_cubes {
 
     #[test]
     fn test_new_cube() {
-        let cube = Cube::new(-5, -5, -5, 5, 5, 5);
-        assert_eq!(Cube::new(-5, -5, -5, 5, 5, 5), cube);
-
-        let cube = Cube::new(5, 5, 5, -5, -5, -5);
-        assert_eq!(Cube::new(-5, -5, -5, 5, 5, 5), cube);
-
         let cube = Cube::new(5, -5, 5, -5, 5, -5);
-        assert_eq!(Cube::new(-5, -5, -5, 5, 5, 5), cube);
+        assert_eq!(Point3d { x: -5, y: -5, z: -5 }, cube.corner1);
+        assert_eq!(Point3d { x: 5, y: 5, z: 5 }, cube.corner2);
+        assert_eq!(crate::Cube::new(Point3::new(-5, -5, -5), Point3::new(5, 5, 5)), cube.into());
     }
 
     #[test]
-    fn test_intersection_partly() {
+    fn test_delegates_to_cuboid() {
         let cube = Cube::new(0, 0, 0, 10, 10, 10);
-        let cube1 = Cube::new(5, 5, 5, 15, 15, 15);
-        assert_eq!(Some(Cube::new(5, 5, 5, 10, 10, 10)), cube.intersection(&cube1));
-
-        let cube = Cube::new(-5, -5, -5, 5, 5, 5);
-        let cube1 = Cube::new(-10, 0, -5, 0, 10, 5);
-        assert_eq!(Some(Cube::new(-5, 0, -5, 0, 5, 5)), cube.intersection(&cube1));
-
-        let cube = Cube::new(-5, -5, -5, 5, 5, 5);
-        let cube1 = Cube::new(-5, -5, -5, 5, 5, 5);
-        assert_eq!(Some(Cube::new(-5, -5, -5, 5, 5, 5)), cube.intersection(&cube1));
-
-        let cube = Cube::new(1, 0, 1, 4, 4, 4);
-        let cube1 = Cube::new(0, 1, 0, 2, 2, 5);
-        assert_eq!(Some(Cube::new(1, 1, 1, 2, 2, 4)), cube.intersection(&cube1));
-    }
-
-    #[test]
-    fn test_intersection_fully() {
-        let cube = Cube::new(-10, -10, -10, 10, 10, 10);
-        let cube1 = Cube::new(-5, -5, -5, 5, 5, 5);
-        assert_eq!(Some(Cube::new(-5, -5, -5, 5, 5, 5)), cube.intersection(&cube1));
-
-        let cube = Cube::new(-5, -5, -5, 5, 5, 5);
-        let cube1 = Cube::new(-10, -10, -10, 10, 10, 10);
-        assert_eq!(Some(Cube::new(-5, -5, -5, 5, 5, 5)), cube.intersection(&cube1));
-    }
-
-    #[test]
-    fn test_no_intersection() {
-        let cube = Cube::new(0, 0, 0, 5, 5, 5);
-        let cube1 = Cube::new(6, 6, 6, 15, 15, 15);
-        assert_eq!(None, cube.intersection(&cube1));
-    }
-
-    #[test]
-    fn test_encloses() {
-        let cube = Cube::new(0, 0, 0, 5, 5, 5);
-        let cube1 = Cube::new(1, 1, 1, 4, 4, 4);
-        assert!(cube.encloses(&cube1));
-    }
-
-    #[test]
-    fn test_volume() {
-        assert_eq!(27, Cube::new(0, 0, 0, 2, 2, 2).volume());
-        assert_eq!(64, Cube::new(-1, -1, -1, 2, 2, 2).volume());
-        assert_eq!(64, Cube::new(2, 2, 2, -1, -1, -1).volume());
-    }
-
-    #[test]
-    fn test_cut_no_intersection() {
-        let cube = Cube::new(0, 0, 0, 5, 5, 5);
-        let cube1 = Cube::new(6, 6, 6, 8, 8, 8);
-        let result = cube.cut(&cube1);
-        assert_eq!(1, result.len());
-        assert_eq!(cube, result.into_iter().nth(0).unwrap());
-    }
-
-    #[test]
-    fn test_cut_inside() {
-        let cube = Cube::new(0, 0, 0, 10, 10, 10);
-        let cube1 = Cube::new(1, 1, 1, 9, 9, 9);
-        let result = cube.cut(&cube1);
-        assert_eq!(6, result.len());
-        assert_eq!(cube.volume() - cube1.volume(), result.iter().map(|c| c.volume()).sum());
-    }
-
-    #[test]
-    fn test_cut_left() {
-        let cube = Cube::new(0, 0, 0, 10, 5, 5);
-        let cube1 = Cube::new(0, 0, 0, 5, 5, 5);
-        let result = cube.cut(&cube1);
-        assert_eq!(1, result.len());
-        assert_eq!(cube.volume() - cube1.volume(), result.iter().map(|c| c.volume()).sum());
-    }
-
-    #[test]
-    fn test_cut_right() {
-        let cube = Cube::new(0, 0, 0, 10, 5, 5);
-        let cube1 = Cube::new(5, 0, 0, 10, 5, 5);
-        let result = cube.cut(&cube1);
-        assert_eq!(1, result.len());
-        assert_eq!(cube.volume() - cube1.volume(), result.iter().map(|c| c.volume()).sum());
-    }
-
-    #[test]
-    fn test_cut_piece() {
-        let cube = Cube::new(0, 0, 0, 10, 10, 10);
-        let cube1 = Cube::new(5, 5, 5, 10, 10, 10);
-        let result = cube.cut(&cube1);
-        assert_eq!(3, result.len());
-        assert_eq!(cube.volume() - cube1.volume(), result.iter().map(|c| c.volume()).sum());
-    }
-
-    #[test]
-    fn test_cut_everything() {
-        let cube = Cube::new(1,1,1,1,1,1);
-        let cube1 = Cube::new(1,1,1,1,1,1);
-        let result = cube.cut(&cube1);
-        assert_eq!(0, result.len());
+        assert_eq!(1331, cube.volume());
+        assert_eq!(Some(Cube::new(5, 5, 5, 10, 10, 10)), cube.intersection(&Cube::new(5, 5, 5, 15, 15, 15)));
+        assert!(cube.encloses(&Cube::new(1, 1, 1, 4, 4, 4)));
+        assert_eq!(vec![Cube::new(0, 0, 0, 10, 10, 5)], cube.cut(&Cube::new(-1, -1, 6, 11, 11, 11)));
     }
 }
@@ -0,0 +1,86 @@
+//! Random inputs for day 22
+
+use std::ops::RangeInclusive;
+
+use aoc_utils::{InputGenerator, Point3, Rng};
+
+use crate::{initialization_area, Cube};
+
+/// Generates reboot steps, `size` is the number of steps
+pub const GENERATOR: InputGenerator = InputGenerator {
+    day: 22,
+    size: "number of steps",
+    default_size: 420,
+    generate,
+};
+
+/// Number of steps inside the initialization procedure area, like in the real input
+const INITIALIZATION_STEPS: usize = 20;
+
+/// Generates `size` reboot steps
+///
+/// The first 20 steps lie within the initialization procedure area (-50..50), all following steps are large
+/// cuboids outside of it. The first step always turns cubes on.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let area = initialization_area();
+    let mut lines = Vec::with_capacity(size);
+    for step in 0..size {
+        let cube = if step < INITIALIZATION_STEPS {
+            random_cube(rng, 50, 5..=50)
+        } else {
+            loop {
+                let cube = random_cube(rng, 100_000, 1_000..=50_000);
+                if cube.intersection(&area).is_none() {
+                    break cube;
+                }
+            }
+        };
+
+        let switch = if step == 0 || rng.chance(0.6) { "on" } else { "off" };
+        let (c1, c2) = (cube.min, cube.max);
+        lines.push(format!("{} x={}..{},y={}..{},z={}..{}", switch, c1.x, c2.x, c1.y, c2.y, c1.z, c2.z));
+    }
+
+    lines.join("\n")
+}
+
+/// Random cuboid within -`limit`..`limit` with edges of the given lengths (limited by the area)
+fn random_cube(rng: &mut Rng, limit: i64, lengths: RangeInclusive<i64>) -> Cube {
+    let mut range = || {
+        let length = rng.range(lengths.clone()).min(2 * limit);
+        let start = rng.range(-limit..=limit - length);
+        (start as i32, (start + length) as i32)
+    };
+
+    let ((x1, x2), (y1, y2), (z1, z2)) = (range(), range(), range());
+    Cube::new(Point3::new(x1, y1, z1), Point3::new(x2, y2, z2))
+}
+
+#[cfg(test)]
+mod tests_generator {
+    use super::*;
+    use crate::{Day22, Switch};
+    use aoc_utils::Solution;
+
+    #[test]
+    fn test_generate() {
+        let commands = Day22::parse(&GENERATOR.generate(22, Some(40))).unwrap();
+        assert_eq!(40, commands.len());
+
+        // Count the cubes of the initialization procedure one by one
+        let mut reactor = vec![false; 101 * 101 * 101];
+        for command in commands.iter().take(INITIALIZATION_STEPS) {
+            let (c1, c2) = (command.cube.min, command.cube.max);
+            for x in c1.x..=c2.x {
+                for y in c1.y..=c2.y {
+                    for z in c1.z..=c2.z {
+                        reactor[((x + 50) * 101 * 101 + (y + 50) * 101 + z + 50) as usize] = command.switch == Switch::On;
+                    }
+                }
+            }
+        }
+
+        assert_eq!(reactor.iter().filter(|on| **on).count() as i64, Day22::part1(&commands));
+        assert!(Day22::part2(&commands) > Day22::part1(&commands));
+    }
+}
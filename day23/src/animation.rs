@@ -0,0 +1,78 @@
+//! Animation of the amphipods organizing themselves
+
+use aoc_utils::{Animation, Budget, Frame, Palette, ParseError, Rgb, Solution, Visualization};
+
+use crate::{min_energy_path, unfold, Burrow, Day23};
+
+/// Shows the moves with the least energy in the unfolded burrow of star 2
+pub const VISUALIZATION: Visualization = Visualization {
+    day: 23,
+    description: "amphipods moving into their rooms with the least energy (unfolded burrow)",
+    create,
+};
+
+fn create(input: &str) -> Result<Box<dyn Animation>, ParseError> {
+    Ok(Box::new(Organizing::new(&unfold(&Day23::parse(input)?))))
+}
+
+/// Burrow states along the solution with the minimum energy
+pub struct Organizing<const N: usize> {
+    states: Vec<(Burrow<N>, u32)>,
+    ix: usize,
+}
+
+impl<const N: usize> Organizing<N> {
+    /// Searches the solution, a burrow without solution is shown as it is
+    pub fn new(burrow: &Burrow<N>) -> Self {
+        let (_, mut states) = min_energy_path(burrow, &Budget::unlimited());
+        if states.is_empty() {
+            states.push((*burrow, 0));
+        }
+
+        Organizing { states, ix: 0 }
+    }
+}
+
+impl<const N: usize> Animation for Organizing<N> {
+    fn frame(&self) -> Frame {
+        let (burrow, energy) = &self.states[self.ix];
+        Frame::new(format!("Move {} of {}, energy {}", self.ix, self.states.len() - 1, energy), &burrow.to_string())
+    }
+
+    fn step(&mut self) -> bool {
+        if self.ix + 1 == self.states.len() {
+            return false;
+        }
+
+        self.ix += 1;
+        true
+    }
+
+    fn palette(&self) -> Palette {
+        &[
+            ('#', Rgb(110, 110, 110)),
+            ('A', Rgb(230, 80, 80)),
+            ('B', Rgb(80, 200, 80)),
+            ('C', Rgb(80, 140, 240)),
+            ('D', Rgb(220, 200, 60)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests_animation {
+    use super::*;
+
+    #[test]
+    fn organize() {
+        let input = "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#A#\n  #########";
+        let mut animation = Organizing::new(&crate::parse_input(input).unwrap());
+        assert_eq!(input, animation.frame().lines.join("\n"));
+        assert!(animation.frame().title.starts_with("Move 0 of "));
+
+        while animation.step() {}
+        let frame = animation.frame();
+        assert!(frame.title.ends_with("energy 12521"));
+        assert_eq!("###A#B#C#D###", frame.lines[2]);
+    }
+}
@@ -0,0 +1,752 @@
+//! Day 23: Amphipod
+#![warn(missing_docs)]
+
+use aoc_utils::{input_lines, trace_event, Budget, ParseError, Solution};
+use std::{
+    cmp::{max, min},
+    fmt::{self, Debug},
+};
+
+pub mod animation;
+
+/// Burrow with four rooms of `N` slots each and the hallway
+///
+/// Cells contain the amphipod type (`A` to `D`) or `.` if empty.
+#[derive(Clone, Copy)]
+pub struct Burrow<const N: usize> {
+    rooms: [[u8; N]; 4],
+    hallway: [u8; 11],
+}
+
+impl<const N: usize> Burrow<N> {
+    /// Creates a burrow with an empty hallway; slot 0 is the bottom of a room
+    pub fn new(rooms: [[u8; N]; 4]) -> Self {
+        Burrow { rooms, hallway: [b'.'; 11] }
+    }
+
+    /// Rooms from left to right, slot 0 is the bottom of a room
+    pub fn rooms(&self) -> &[[u8; N]; 4] {
+        &self.rooms
+    }
+
+    /// Hallway from left to right
+    pub fn hallway(&self) -> &[u8; 11] {
+        &self.hallway
+    }
+}
+
+impl<const N: usize> Debug for Burrow<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Diagram on its own lines, so that it stays readable in `dbg!` output
+        write!(f, "\n{}\n", self)
+    }
+}
+
+impl<const N: usize> fmt::Display for Burrow<N> {
+    /// Burrow diagram as in the puzzle input
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "#############")?;
+        writeln!(f, "#{}#", String::from_utf8_lossy(&self.hallway))?;
+        for slot in (0..N).rev() {
+            let row: Vec<String> = self.rooms.iter().map(|room| (room[slot] as char).to_string()).collect();
+            if slot == N - 1 {
+                writeln!(f, "###{}###", row.join("#"))?;
+            } else {
+                writeln!(f, "  #{}#", row.join("#"))?;
+            }
+        }
+        write!(f, "  #########")
+    }
+}
+
+/// Parses input (burrow diagram with two amphipods per room)
+pub fn parse_input(input: &str) -> Result<Burrow<2>, ParseError> {
+    const AMPHIPODS: [(&str, u8); 4] = [("A", b'A'), ("B", b'B'), ("C", b'C'), ("D", b'D')];
+
+    let mut rows = Vec::new();
+    let mut last_line = 0;
+    for mut line in input_lines(input) {
+        last_line = line.line();
+        let mut row = Vec::new();
+        while !line.is_at_end() {
+            if line.try_literal("#") || line.try_literal(".") || line.try_literal(" ") {
+                continue;
+            }
+            row.push(line.one_of(&AMPHIPODS).map_err(|_| line.expected("amphipod 'A' to 'D', wall or open space"))?);
+        }
+
+        if row.is_empty() {
+            continue;
+        }
+        if row.len() != 4 {
+            return Err(ParseError::new(last_line, 1, format!("expected four amphipods, found {}", row.len())));
+        }
+        if rows.len() == 2 {
+            return Err(ParseError::new(last_line, 1, "expected two rows of amphipods"));
+        }
+        rows.push((last_line, row));
+    }
+
+    if rows.len() != 2 {
+        return Err(ParseError::new(last_line + 1, 1, "expected two rows of amphipods"));
+    }
+    for (name, amphipod) in AMPHIPODS {
+        let count = rows.iter().flat_map(|(_, row)| row).filter(|&&a| a == amphipod).count();
+        if count != 2 {
+            return Err(ParseError::new(rows[1].0, 1, format!("expected two amphipods '{}', found {}", name, count)));
+        }
+    }
+
+    // Slot 0 is the bottom of a room
+    let mut rooms = [[b'.'; 2]; 4];
+    for (r, room) in rooms.iter_mut().enumerate() {
+        *room = [rows[1].1[r], rows[0].1[r]];
+    }
+
+    Ok(Burrow::new(rooms))
+}
+
+/// Inserts the two folded lines of the diagram for star 2
+pub fn unfold(burrow: &Burrow<2>) -> Burrow<4> {
+    const FOLDED: [[u8; 2]; 4] = [[b'D', b'D'], [b'B', b'C'], [b'A', b'B'], [b'C', b'A']];
+
+    let mut rooms = [[b'.'; 4]; 4];
+    for (r, room) in rooms.iter_mut().enumerate() {
+        *room = [burrow.rooms[r][0], FOLDED[r][0], FOLDED[r][1], burrow.rooms[r][1]];
+    }
+
+    Burrow::new(rooms)
+}
+
+/// Minimum energy to organize the amphipods
+///
+/// If the budget is exhausted before the search is complete, the best solution found so far is returned
+/// (`u32::MAX` if there is none yet).
+pub fn min_energy<const N: usize>(burrow: &Burrow<N>, budget: &Budget) -> u32 {
+    let mut burrow = *burrow;
+    let mut min_energy = u32::MAX;
+    solve_1_impl(&mut burrow, 0, &mut min_energy, budget);
+    min_energy
+}
+
+/// Minimum energy to organize the amphipods and the states of the burrow on the way with the energy spent so far
+///
+/// The states start with the given burrow and end with the organized one; amphipods that can move straight into
+/// their rooms are moved together in one state. The states are empty if there is no solution.
+pub fn min_energy_path<const N: usize>(burrow: &Burrow<N>, budget: &Budget) -> (u32, Vec<(Burrow<N>, u32)>) {
+    let mut burrow = *burrow;
+    let mut min_energy = u32::MAX;
+    let mut trail = Trail::new(true);
+    search(&mut burrow, 0, &mut min_energy, budget, &mut trail);
+    (min_energy, trail.best)
+}
+
+/// States of the burrow along the current search path and along the best solution found so far
+struct Trail<const N: usize> {
+    enabled: bool,
+    current: Vec<(Burrow<N>, u32)>,
+    best: Vec<(Burrow<N>, u32)>,
+}
+
+impl<const N: usize> Trail<N> {
+    fn new(enabled: bool) -> Self {
+        Trail { enabled, current: Vec::new(), best: Vec::new() }
+    }
+
+    /// Appends a state, returns the length before to undo it with [`Trail::truncate`]
+    fn push(&mut self, burrow: &Burrow<N>, total_energy: u32) -> usize {
+        let mark = self.current.len();
+        if self.enabled {
+            self.current.push((*burrow, total_energy));
+        }
+
+        mark
+    }
+
+    fn truncate(&mut self, mark: usize) {
+        self.current.truncate(mark);
+    }
+
+    fn save_best(&mut self) {
+        if self.enabled {
+            self.best.clone_from(&self.current);
+        }
+    }
+}
+
+/// Exhaustive search for the minimum energy
+///
+/// Stops early once the budget is exhausted; `min_energy` is then the best solution found so far.
+fn solve_1_impl<const N: usize>(burrow: &mut Burrow<N>, total_energy: u32, min_energy: &mut u32, budget: &Budget) {
+    search(burrow, total_energy, min_energy, budget, &mut Trail::new(false));
+}
+
+fn search<const N: usize>(burrow: &mut Burrow<N>, total_energy: u32, min_energy: &mut u32, budget: &Budget, trail: &mut Trail<N>) {
+    let mark = trail.push(burrow, total_energy);
+    explore(burrow, total_energy, min_energy, budget, trail);
+    trail.truncate(mark);
+}
+
+fn explore<const N: usize>(burrow: &mut Burrow<N>, mut total_energy: u32, min_energy: &mut u32, budget: &Budget, trail: &mut Trail<N>) {
+    fn check_solved<const N: usize>(
+        burrow: &mut Burrow<N>,
+        move_fn: fn(&mut Burrow<N>) -> u32,
+        total_energy: &mut u32,
+        min_energy: &mut u32,
+        budget: &Budget,
+        trail: &mut Trail<N>,
+    ) -> (bool, bool) {
+        let energy = move_fn(burrow);
+        let moved_anything = energy != 0;
+        *total_energy += energy;
+        if moved_anything {
+            trail.push(burrow, *total_energy);
+        }
+
+        if *total_energy >= *min_energy {
+            // Total energy already exceeds previous minimum -> stop
+            return (moved_anything, true);
+        }
+
+        if solved(&burrow.rooms) {
+            // Solved with new energy minimum -> stop
+            *min_energy = *total_energy;
+            trace_event!("solution", energy = *min_energy);
+            trail.save_best();
+            budget.report_best(*min_energy);
+            return (moved_anything, true);
+        }
+
+        (moved_anything, false)
+    }
+
+    if budget.is_exhausted() {
+        return;
+    }
+
+    loop {
+        let result1 = check_solved(burrow, move_between_rooms, &mut total_energy, min_energy, budget, trail);
+        if result1.1 {
+            return;
+        }
+
+        let result2 = check_solved(burrow, move_from_hallway_into_rooms, &mut total_energy, min_energy, budget, trail);
+        if result2.1 {
+            return;
+        }
+
+        if !result1.0 && !result2.0 {
+            break;
+        }
+    }
+
+    for r in 0..4 {
+        for slot in (0..N).rev() {
+            if burrow.rooms[r][slot] == b'.' {
+                continue;
+            }
+
+            for hallway_ix in (0..burrow.hallway.len()).filter(|i| *i < 2 || *i > 8 || (*i - 2) % 2 != 0) {
+                let mut new_burrow = *burrow;
+                let energy = move_into_hallway(&mut new_burrow, r, slot, hallway_ix);
+                if energy != 0 {
+                    search(&mut new_burrow, total_energy + energy, min_energy, budget, trail);
+                }
+            }
+        }
+    }
+}
+
+fn move_between_rooms<const N: usize>(burrow: &mut Burrow<N>) -> u32 {
+    let mut total_energy = 0u32;
+    loop {
+        let mut moved_something = false;
+        for r in 0..4 {
+            for slot in (0..N).rev() {
+                let pod = burrow.rooms[r][slot];
+
+                if pod == b'.' {
+                    // Cannot move empty slot
+                    continue;
+                }
+
+                let target_room = (pod - b'A') as usize;
+                if r == target_room {
+                    // Pod already at correct position
+                    continue;
+                }
+
+                if !can_move_out(&burrow.rooms[r], slot, r) {
+                    // If we are handling the back slot, make sure front slot is empty.
+                    // Otherwise, the ampihpod cannot get out.
+                    continue;
+                }
+
+                if let Some(target_slot) = can_move_into(&burrow.rooms[target_room], pod) {
+                    if is_way_between_rooms_free(&burrow.hallway, r, target_room) {
+                        moved_something = true;
+                        burrow.rooms[r][slot] = b'.';
+                        burrow.rooms[target_room][target_slot] = pod;
+
+                        let energy_for_moving_out_of_room = (N - slot) as u32;
+                        let energy_for_moving_between_rooms = room_to_hallway_index(target_room).abs_diff(room_to_hallway_index(r)) as u32;
+                        let energy_for_moving_into_room = (N - target_slot) as u32;
+                        total_energy += energy_per_step(target_room) * (energy_for_moving_out_of_room + energy_for_moving_between_rooms + energy_for_moving_into_room);
+                    }
+                }
+            }
+        }
+
+        if !moved_something {
+            break;
+        }
+    }
+
+    total_energy
+}
+
+fn can_move_into<const N: usize>(room: &[u8; N], pod: u8) -> Option<usize> {
+    for item in room.iter().enumerate() {
+        if *item.1 == b'.' {
+            // Found first empty slot
+            return Some(item.0);
+        }
+
+        if *item.1 != pod {
+            // Found a slot with a different pod -> cannot move into
+            return None;
+        }
+    }
+
+    None
+}
+
+fn can_move_out<const N: usize>(room: &[u8; N], slot: usize, from_room: usize) -> bool {
+    let way_out_is_free = slot == N - 1 || (slot + 1..N).all(|s| room[s] == b'.');
+    let already_in_right_spot = (room[slot] - b'A') as usize == from_room;
+    let foreign_pods_behind = (0..slot).any(|s| room[s] != room[slot]);
+    way_out_is_free && (!already_in_right_spot || foreign_pods_behind)
+}
+
+fn move_from_hallway_into_rooms<const N: usize>(burrow: &mut Burrow<N>) -> u32 {
+    let mut total_energy = 0u32;
+    loop {
+        let mut moved_something = false;
+        let items_in_hallway: Vec<(usize, u8)> = burrow.hallway.into_iter().enumerate().filter(|h| h.1 != b'.').collect();
+        for h in items_in_hallway {
+            let pod = h.1;
+            let target_room = (pod - b'A') as usize;
+
+            if let Some(target_slot) = can_move_into(&burrow.rooms[target_room], pod) {
+                if is_way_from_hallway_into_room_free(&burrow.hallway, h.0, target_room) {
+                    moved_something = true;
+                    burrow.hallway[h.0] = b'.';
+                    burrow.rooms[target_room][target_slot] = pod;
+
+                    let energy_for_moving_to_room = room_to_hallway_index(target_room).abs_diff(h.0) as u32;
+                    let energy_for_moving_into_room = (N - target_slot) as u32;
+                    total_energy += energy_per_step(target_room) * (energy_for_moving_to_room + energy_for_moving_into_room);
+                }
+            }
+        }
+
+        if !moved_something {
+            break;
+        }
+    }
+
+    total_energy
+}
+
+fn move_into_hallway<const N: usize>(burrow: &mut Burrow<N>, room: usize, slot: usize, hallway_ix: usize) -> u32 {
+    let pod = burrow.rooms[room][slot];
+
+    if pod == b'.' {
+        // Cannot move empty slot
+        return 0;
+    }
+
+    if !can_move_out(&burrow.rooms[room], slot, room) {
+        // If we are handling the back slot, make sure front slot is empty.
+        // Otherwise, the ampihpod cannot get out.
+        return 0;
+    }
+
+    let target_room = (pod - b'A') as usize;
+    if room == target_room && slot == 0 {
+        // Pod already at correct position
+        return 0;
+    }
+
+    if burrow.hallway[hallway_ix] != b'.' {
+        // Target index in hallway allready occupied
+        return 0;
+    }
+
+    if !is_way_from_hallway_into_room_free(&burrow.hallway, hallway_ix, room) {
+        return 0;
+    }
+
+    burrow.rooms[room][slot] = b'.';
+    burrow.hallway[hallway_ix] = pod;
+
+    let energy_for_moving_out_of_room = (N - slot) as u32;
+    let energy_for_moving_from_room = room_to_hallway_index(room).abs_diff(hallway_ix) as u32;
+    energy_per_step((pod - b'A') as usize) * (energy_for_moving_out_of_room + energy_for_moving_from_room)
+}
+
+fn energy_per_step(target_room: usize) -> u32 {
+    10u32.pow(target_room as u32)
+}
+
+fn room_to_hallway_index(room: usize) -> usize {
+    2 + room * 2
+}
+
+fn is_way_between_rooms_free(hallway: &[u8; 11], from: usize, to: usize) -> bool {
+    hallway[room_to_hallway_index(min(from, to))..=room_to_hallway_index(max(from, to))].iter().all(|h| *h == b'.')
+}
+
+fn is_way_from_hallway_into_room_free(hallway: &[u8; 11], mut from: usize, to_room: usize) -> bool {
+    let to = room_to_hallway_index(to_room);
+    if to < from {
+        from -= 1;
+    } else {
+        from += 1;
+    }
+
+    hallway[min(from, to)..=max(from, to)].iter().all(|h| *h == b'.')
+}
+
+fn solved<const N: usize>(rooms: &[[u8; N]; 4]) -> bool {
+    for room in rooms.iter().enumerate() {
+        for i in 0..N {
+            if room.1[i] != b'A' + room.0 as u8 {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Solution for day 23
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: i32 = 23;
+    type Input = Burrow<2>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(burrow: &Self::Input) -> Self::Answer1 {
+        min_energy(burrow, &Budget::current())
+    }
+
+    fn part2(burrow: &Self::Input) -> Self::Answer2 {
+        min_energy(&unfold(burrow), &Budget::current())
+    }
+}
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    const TEST_INPUT: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########
+";
+
+    #[test]
+    fn test_parse_input() {
+        let b = parse_input(TEST_INPUT).unwrap();
+        assert_eq!([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']], b.rooms);
+        assert_eq!([b'.'; 11], b.hallway);
+    }
+
+    #[test]
+    fn test_parse_malformed_input() {
+        let err = parse_input(&TEST_INPUT.replace("#A#D", "#E#D")).unwrap_err();
+        assert_eq!("line 4, column 4: expected amphipod 'A' to 'D', wall or open space, found 'E#D#C#A#'", err.to_string());
+        let err = parse_input(&TEST_INPUT.replace("#A#D#C#A#", "#A#D#C#")).unwrap_err();
+        assert_eq!("line 4, column 1: expected four amphipods, found 3", err.to_string());
+        let err = parse_input("#############\n#...........#\n###B#C#B#D###\n").unwrap_err();
+        assert_eq!("line 4, column 1: expected two rows of amphipods", err.to_string());
+        let err = parse_input(&TEST_INPUT.replace("#A#D", "#B#D")).unwrap_err();
+        assert_eq!("line 4, column 1: expected two amphipods 'A', found 1", err.to_string());
+    }
+
+    #[test]
+    fn test_not_solved() {
+        let rooms = [[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']];
+        assert!(!solved(&rooms));
+    }
+
+    #[test]
+    fn test_not_solved_order() {
+        let rooms = [[b'A', b'A'], [b'C', b'C'], [b'B', b'B'], [b'D', b'D']];
+        assert!(!solved(&rooms));
+    }
+
+    #[test]
+    fn test_solved() {
+        let rooms = [[b'A', b'A'], [b'B', b'B'], [b'C', b'C'], [b'D', b'D']];
+        assert!(solved(&rooms));
+    }
+
+    #[test]
+    fn test_room_to_hallway_index() {
+        assert_eq!(2, room_to_hallway_index(0));
+        assert_eq!(4, room_to_hallway_index(1));
+        assert_eq!(6, room_to_hallway_index(2));
+        assert_eq!(8, room_to_hallway_index(3));
+    }
+
+    #[test]
+    fn test_is_way_free() {
+        let mut hallway = [b'.'; 11];
+        assert!(is_way_between_rooms_free(&hallway, 0, 3));
+
+        hallway[2] = b'B';
+        assert!(!is_way_between_rooms_free(&hallway, 0, 1));
+        assert!(is_way_between_rooms_free(&hallway, 2, 3));
+
+        hallway[8] = b'B';
+        assert!(!is_way_between_rooms_free(&hallway, 2, 3));
+    }
+
+    #[test]
+    fn test_is_way_from_hallway_free() {
+        let mut hallway = [b'.'; 11];
+        assert!(is_way_from_hallway_into_room_free(&hallway, 0, 3));
+
+        hallway[3] = b'B';
+        assert!(!is_way_from_hallway_into_room_free(&hallway, 1, 1));
+        assert!(is_way_from_hallway_into_room_free(&hallway, 5, 3));
+
+        hallway[8] = b'B';
+        assert!(!is_way_from_hallway_into_room_free(&hallway, 10, 2));
+    }
+
+    #[test]
+    fn test_is_way_from_hallway_free_2() {
+        let mut hallway = [b'.'; 11];
+        hallway[5] = b'D';
+        hallway[7] = b'A';
+        hallway[9] = b'D';
+        assert!(!is_way_from_hallway_into_room_free(&hallway, 5, 3));
+        assert!(!is_way_from_hallway_into_room_free(&hallway, 7, 0));
+    }
+
+    #[test]
+    fn test_energy_per_step() {
+        assert_eq!(1, energy_per_step(0));
+        assert_eq!(10, energy_per_step(1));
+        assert_eq!(100, energy_per_step(2));
+        assert_eq!(1000, energy_per_step(3));
+    }
+
+    #[test]
+    fn test_move_into_rooms_1() {
+        let mut b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'.'], [b'A', b'D']]);
+        let total_energy = move_between_rooms(&mut b);
+        assert_eq!(400, total_energy);
+        assert_eq!([[b'A', b'B'], [b'D', b'.'], [b'C', b'C'], [b'A', b'D']], b.rooms);
+    }
+
+    #[test]
+    fn test_move_into_rooms_2() {
+        let mut b = Burrow::new([[b'A', b'.'], [b'B', b'B'], [b'D', b'D'], [b'.', b'.']]);
+        let total_energy = move_between_rooms(&mut b);
+        assert_eq!(10_000, total_energy);
+        assert_eq!([[b'A', b'.'], [b'B', b'B'], [b'.', b'.'], [b'D', b'D']], b.rooms);
+    }
+
+    #[test]
+    fn test_move_from_halloway_into_room_1() {
+        let mut b = Burrow::new([[b'A', b'.'], [b'B', b'B'], [b'C', b'C'], [b'.', b'.']]);
+        b.hallway[5] = b'D';
+        b.hallway[7] = b'D';
+        b.hallway[9] = b'A';
+        let total_energy = move_from_hallway_into_rooms(&mut b);
+        assert_eq!(7_000 + 8, total_energy);
+        assert_eq!([[b'A', b'A'], [b'B', b'B'], [b'C', b'C'], [b'D', b'D']], b.rooms);
+        assert!(b.hallway.into_iter().all(|h| h == b'.'));
+    }
+
+    #[test]
+    fn test_move_from_halloway_into_room_2() {
+        let mut b = Burrow::new([[b'A', b'B'], [b'.', b'B'], [b'C', b'C'], [b'.', b'.']]);
+        b.hallway[5] = b'A';
+        let total_energy = move_from_hallway_into_rooms(&mut b);
+        assert_eq!(0, total_energy);
+        assert_eq!([[b'A', b'B'], [b'.', b'B'], [b'C', b'C'], [b'.', b'.']], b.rooms);
+        assert_eq!(b'A', b.hallway[5]);
+    }
+
+    #[test]
+    fn test_move_into_hallway_1() {
+        let mut b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]);
+        let total_energy = move_into_hallway(&mut b, 2, 1, 3);
+        assert_eq!(40, total_energy);
+        assert_eq!([[b'A', b'B'], [b'D', b'C'], [b'C', b'.'], [b'A', b'D']], b.rooms);
+        assert_eq!(b'B', b.hallway[3]);
+    }
+
+    #[test]
+    fn test_move_into_hallway_2() {
+        let mut b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]);
+        let total_energy = move_into_hallway(&mut b, 2, 0, 3);
+        assert_eq!(0, total_energy);
+        assert_eq!([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']], b.rooms);
+        assert!(b.hallway.into_iter().all(|h| h == b'.'));
+    }
+
+    #[test]
+    fn test_move_into_hallway_3() {
+        let mut b = Burrow::new([[b'A', b'.'], [b'.', b'B'], [b'C', b'C'], [b'.', b'.']]);
+        b.hallway[0] = b'B';
+        let total_energy = move_into_hallway(&mut b, 0, 0, 0);
+        assert_eq!(0, total_energy);
+        assert_eq!([[b'A', b'.'], [b'.', b'B'], [b'C', b'C'], [b'.', b'.']], b.rooms);
+        assert_eq!(b'B', b.hallway[0]);
+    }
+
+    #[test]
+    fn test_move_into_hallway_4() {
+        let mut b = Burrow::new([[b'A', b'.'], [b'.', b'B'], [b'C', b'C'], [b'.', b'.']]);
+        let total_energy = move_into_hallway(&mut b, 0, 0, 0);
+        assert_eq!(0, total_energy);
+    }
+
+    #[test]
+    fn test_move_into_hallway_5() {
+        let mut b = Burrow::new([[b'A', b'A'], [b'.', b'B'], [b'C', b'C'], [b'.', b'.']]);
+        let total_energy = move_into_hallway(&mut b, 0, 0, 1);
+        assert_eq!(0, total_energy);
+    }
+
+    #[test]
+    fn test_move_into_hallway_6() {
+        let mut b = Burrow::new([[b'B', b'A'], [b'.', b'B'], [b'C', b'C'], [b'.', b'.']]);
+        let total_energy = move_into_hallway(&mut b, 0, 1, 0);
+        assert!(total_energy > 0);
+    }
+
+    #[test]
+    fn test_solve_1() {
+        let mut b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]);
+        let mut min_energy = u32::MAX;
+        solve_1_impl(&mut b, 0, &mut min_energy, &Budget::unlimited());
+        assert_eq!(12521, min_energy);
+    }
+
+    #[test]
+    fn test_solve_1_budget() {
+        let b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]);
+        let budget = Budget::unlimited();
+        let mut min_energy = u32::MAX;
+        solve_1_impl(&mut b.clone(), 0, &mut min_energy, &budget);
+        assert_eq!(Some("12521".to_string()), budget.best());
+
+        budget.cancel();
+        let mut min_energy = u32::MAX;
+        solve_1_impl(&mut b.clone(), 0, &mut min_energy, &budget);
+        assert_eq!(u32::MAX, min_energy);
+    }
+
+    #[test]
+    fn test_min_energy_path() {
+        let b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]);
+        let (energy, path) = min_energy_path(&b, &Budget::unlimited());
+        assert_eq!(12521, energy);
+        assert_eq!(b.rooms, path[0].0.rooms);
+        assert_eq!(0, path[0].1);
+        assert!(solved(&path.last().unwrap().0.rooms));
+        assert_eq!(12521, path.last().unwrap().1);
+        assert!(path.windows(2).all(|w| w[0].1 < w[1].1));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]);
+        b.hallway[3] = b'B';
+        b.rooms[2][1] = b'.';
+        assert_eq!("#############\n#...B.......#\n###B#C#.#D###\n  #A#D#C#A#\n  #########", b.to_string());
+
+        let b = Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]);
+        assert_eq!(b.rooms, parse_input(&b.to_string()).unwrap().rooms);
+    }
+
+    #[test]
+    fn test_can_move_into() {
+        let r = [b'.', b'.', b'.', b'.'];
+        assert_eq!(Some(0), can_move_into(&r, b'A'));
+
+        let r = [b'A', b'.', b'.', b'.'];
+        assert_eq!(Some(1), can_move_into(&r, b'A'));
+
+        let r = [b'B', b'.', b'.', b'.'];
+        assert_eq!(None, can_move_into(&r, b'A'));
+
+        let r = [b'A', b'A', b'A', b'A'];
+        assert_eq!(None, can_move_into(&r, b'A'));
+    }
+
+    #[test]
+    fn test_can_move_out() {
+        let r = [b'A', b'.', b'.', b'.'];
+        assert!(can_move_out(&r, 0, 1));
+
+        let r = [b'A', b'B', b'.', b'.'];
+        assert!(!can_move_out(&r, 0, 1));
+
+        let r = [b'A', b'A', b'A', b'B'];
+        assert!(can_move_out(&r, 3, 2));
+
+        let r = [b'A', b'A', b'A', b'.'];
+        assert!(!can_move_out(&r, 2, 0));
+
+        let r = [b'D', b'D'];
+        assert!(!can_move_out(&r, 1, 3));
+    }
+}
+
+/// Tests for star 2
+#[cfg(test)]
+mod tests_star2 {
+    use super::*;
+
+    #[test]
+    fn test_unfold() {
+        let b = unfold(&Burrow::new([[b'A', b'B'], [b'D', b'C'], [b'C', b'B'], [b'A', b'D']]));
+        assert_eq!([[b'A', b'D', b'D', b'B'], [b'D', b'B', b'C', b'C'], [b'C', b'A', b'B', b'B'], [b'A', b'C', b'A', b'D']], b.rooms);
+    }
+
+    #[test]
+    fn test_solve_2_1() {
+        let mut b = Burrow::new([[b'A', b'D', b'D', b'B'], [b'D', b'B', b'C', b'C'], [b'C', b'A', b'B', b'B'], [b'A', b'C', b'A', b'D']]);
+        let mut min_energy = u32::MAX;
+        solve_1_impl(&mut b, 0, &mut min_energy, &Budget::unlimited());
+        assert_eq!(44169, min_energy);
+    }
+
+    #[test]
+    fn test_solve_2_2() {
+        let mut b = Burrow::new([[b'A', b'D', b'.', b'.'], [b'B', b'B', b'B', b'B'], [b'C', b'C', b'C', b'C'], [b'D', b'D', b'.', b'.']]);
+        b.hallway[10] = b'D';
+        b.hallway[9] = b'A';
+        b.hallway[0] = b'A';
+        b.hallway[1] = b'A';
+        let mut min_energy = u32::MAX;
+        solve_1_impl(&mut b, 0, &mut min_energy, &Budget::unlimited());
+        assert_eq!(3000 + 8 + 7000 + 4 + 4 + 4000, min_energy);
+    }
+}
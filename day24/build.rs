@@ -6,53 +6,94 @@ fn main() {
     let out_dir = env::var_os("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("generated.rs");
 
-    let input = fs::read_to_string("./input.txt").unwrap();
+    // The MONAD program is compiled into the binary, so alternate inputs have to be selected at build time
+    println!("cargo:rerun-if-env-changed=AOC_INPUT_DIR");
+    let input_file = match env::var_os("AOC_INPUT_DIR") {
+        Some(dir) if !dir.is_empty() => Path::new(&dir).join("day24").join("input.txt"),
+        _ => Path::new("input.txt").to_path_buf(),
+    };
+    println!("cargo:rerun-if-changed={}", input_file.display());
+
+    let input = fs::read_to_string(&input_file).unwrap_or_else(|e| panic!("cannot read {}: {}", input_file.display(), e));
 
     let mut program = String::new();
 
-    program.push_str("pub type Calculator = fn(z: i64, w: i64) -> i64;
+    program.push_str("/// Block of the MONAD program: calculates `z` from the previous `z` and the input digit `w`
+    pub type Calculator = fn(z: i64, w: i64) -> i64;
 
+    /// Blocks of the MONAD program, one per digit of the model number
     pub const CALCULATORS: [Calculator; 14] = [\n");
 
-    let mut ix = 14usize;
-    for line in input.split('\n') {
-        match &line[..3] {
-            "inp" => {
-                if ix < 14 {
-                    program.push_str("z\n},\n");
+    // CRLF line endings, surrounding whitespace and empty lines (e.g. a final newline) are ignored
+    let mut blocks = 0usize;
+    let mut instructions = Vec::new();
+    for (number, line) in input.lines().enumerate().map(|(ix, l)| (ix + 1, l.trim())).filter(|(_, l)| !l.is_empty()) {
+        let (instruction, register, operand) = parse_instruction(line)
+            .unwrap_or_else(|| panic!("{}:{}: invalid instruction '{}'", input_file.display(), number, line));
+        if instruction != "inp" && blocks == 0 {
+            panic!("{}:{}: expected 'inp w' before '{}'", input_file.display(), number, line);
+        }
+
+        instructions.push(line.split_whitespace().collect::<Vec<_>>().join(" "));
 
+        match (instruction, operand) {
+            ("inp", None) => {
+                if blocks > 0 {
+                    program.push_str("z\n},\n");
                 }
                 program.push_str("
                     |mut z: i64, w: i64| {
                         let mut x;
                         let mut y;\n");
-                ix -= 1;
-            },
-            "add" => program.push_str(&format!("{} += {};\n", line.as_bytes()[4] as char, &line[6..])),
-            "mul" => {
-                let operand = &line[6..];
-                match operand {
-                    "0" => program.push_str(&format!("{} = 0;\n", line.as_bytes()[4] as char)),
-                    _ => program.push_str(&format!("{} *= {};\n", line.as_bytes()[4] as char, operand)),
-                }
-            },
-            "div" => {
-                let operand = &line[6..];
-                if operand != "1" {
-                    program.push_str(&format!("{} /= {};\n", line.as_bytes()[4] as char, &line[6..]));
-                }
+                blocks += 1;
             },
-            "mod" => program.push_str(&format!("{} %= {};\n", line.as_bytes()[4] as char, &line[6..])),
-            "eql" => program.push_str(&format!("{0} = if {0} == {1} {{ 1 }} else {{ 0 }};\n", line.as_bytes()[4] as char, &line[6..])),
-            _ => panic!()
+            ("add", Some(operand)) => program.push_str(&format!("{} += {};\n", register, operand)),
+            ("mul", Some("0")) => program.push_str(&format!("{} = 0;\n", register)),
+            ("mul", Some(operand)) => program.push_str(&format!("{} *= {};\n", register, operand)),
+            ("div", Some("1")) => {},
+            ("div", Some(operand)) => program.push_str(&format!("{} /= {};\n", register, operand)),
+            ("mod", Some(operand)) => program.push_str(&format!("{} %= {};\n", register, operand)),
+            ("eql", Some(operand)) => program.push_str(&format!("{0} = if {0} == {1} {{ 1 }} else {{ 0 }};\n", register, operand)),
+            _ => panic!("{}:{}: invalid instruction '{}'", input_file.display(), number, line),
         }
     }
 
+    if blocks != 14 {
+        panic!("{}: expected 14 'inp' instructions (one per digit), found {}", input_file.display(), blocks);
+    }
+
     program.push_str("z\n}\n];\n");
 
+    program.push_str("
+    /// Instructions of the MONAD program compiled into [`CALCULATORS`], used to check the input given at runtime
+    pub const PROGRAM: &[&str] = &[\n");
+    for instruction in instructions {
+        program.push_str(&format!("{:?},\n", instruction));
+    }
+    program.push_str("];\n");
+
     fs::write(
         &dest_path,
         program
     ).unwrap();
     println!("cargo:rerun-if-changed=build.rs");
 }
+
+/// Splits an instruction like `add x -3` into name, register and operand (`None` for `inp`)
+///
+/// Returns `None` if the register is not one of `w`, `x`, `y`, `z` or the operand is neither a register nor a number.
+fn parse_instruction(line: &str) -> Option<(&str, char, Option<&str>)> {
+    let mut parts = line.split_whitespace();
+    let instruction = parts.next()?;
+    let register = match parts.next()? {
+        r @ ("w" | "x" | "y" | "z") => r.chars().next()?,
+        _ => return None,
+    };
+    let operand = match (instruction, parts.next()) {
+        ("inp", None) => None,
+        (_, Some(operand)) if matches!(operand, "w" | "x" | "y" | "z") || operand.parse::<i64>().is_ok() => Some(operand),
+        _ => return None,
+    };
+
+    parts.next().is_none().then_some((instruction, register, operand))
+}
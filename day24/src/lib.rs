@@ -0,0 +1,160 @@
+//! Day 24: Arithmetic Logic Unit
+//!
+//! The MONAD program of the input is compiled into Rust functions by the build script.
+#![warn(missing_docs)]
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use aoc_utils::{input_lines, Budget, ParseError, Solution};
+
+include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+/// Checks that the input is the MONAD program compiled in by build.rs
+///
+/// Other programs cannot be solved without rebuilding with their input (see `AOC_INPUT_DIR`).
+pub fn check_program(input: &str) -> Result<(), ParseError> {
+    let mut lines = input_lines(input);
+    for expected in PROGRAM {
+        let Some(line) = lines.next() else {
+            return Err(ParseError::new(input.lines().count() + 1, 1, format!("expected '{}' of the compiled MONAD program, found end of input", expected)));
+        };
+
+        if line.rest().split_whitespace().ne(expected.split(' ')) {
+            return Err(line.expected(format!("'{}' of the compiled MONAD program (rebuild with AOC_INPUT_DIR to solve other inputs)", expected)));
+        }
+    }
+
+    match lines.next() {
+        Some(line) => Err(line.expected("end of the compiled MONAD program")),
+        None => Ok(()),
+    }
+}
+
+/// Finds all model numbers accepted by the MONAD program
+///
+/// Returns `None` if the budget is exhausted before the search is complete.
+pub fn find_model_numbers(budget: &Budget) -> Option<Vec<i64>> {
+    let mut res = HashMap::<i64, i64>::new();
+    for d in 1..=9 {
+        res.insert(d, CALCULATORS[CALCULATORS.len() - 14](0, d));
+    }
+
+    let mut max = 26;
+    for c in (0..=12).rev() {
+        max = match c {
+            12 => max * 26,
+            11 => max * 26,
+            10 => max / 26,
+            9 => max / 26,
+            8 => max * 26,
+            7 => max * 26,
+            6 => max * 26,
+            5 => max / 26,
+            4 => max / 26,
+            3 => max / 26,
+            2 => max * 26,
+            1 => max / 26,
+            0 => max / 26,
+            _ => panic!()
+        };
+        let mut new_res = HashMap::<i64, i64>::new();
+        for r in res {
+            if budget.is_exhausted() {
+                return None;
+            }
+
+            for d in 1..=9 {
+                let check = r.0 * 10 + d;
+                let z = CALCULATORS[CALCULATORS.len() - 1 - c](r.1, d);
+                if z < max {
+                    new_res.insert(check, z);
+                }
+            }
+        }
+
+        res = new_res;
+    }
+
+    Some(res.into_iter().filter(|item| item.1 == 0i64).map(|item| item.0).collect())
+}
+
+/// Model numbers accepted by the MONAD program, searched by the first part that needs them
+///
+/// The search is the expensive step, so it is measured as part of solving rather than parsing.
+#[derive(Debug, Default)]
+pub struct ModelNumbers(OnceLock<Option<Vec<i64>>>);
+
+impl ModelNumbers {
+    /// All accepted model numbers, searched on the first call (see [`find_model_numbers`])
+    ///
+    /// Panics if the search was cancelled.
+    pub fn get(&self) -> &[i64] {
+        self.0.get_or_init(|| find_model_numbers(&Budget::current())).as_deref().expect("search for model numbers was cancelled")
+    }
+}
+
+/// Solution for day 24
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: i32 = 24;
+    type Input = ModelNumbers;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    /// The MONAD program is compiled into the binary by build.rs, the input only has to match it
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        check_program(input)?;
+        Ok(ModelNumbers::default())
+    }
+
+    /// Panics if the search was cancelled or the MONAD program accepts no model number
+    fn part1(monads: &Self::Input) -> Self::Answer1 {
+        *monads.get().iter().max().expect("no model number is accepted by the MONAD program")
+    }
+
+    fn part2(monads: &Self::Input) -> Self::Answer2 {
+        *monads.get().iter().min().expect("no model number is accepted by the MONAD program")
+    }
+}
+
+/// Tests for the input check
+#[cfg(test)]
+mod tests_input {
+    use super::*;
+    use aoc_utils::line_ending_variants;
+
+    #[test]
+    fn test_compiled_program() {
+        for input in line_ending_variants(&PROGRAM.join("\n")) {
+            assert_eq!(Ok(()), check_program(&input));
+        }
+    }
+
+    #[test]
+    fn test_other_program() {
+        let mut program = PROGRAM.to_vec();
+        program[3] = "add w 99";
+        let err = check_program(&program.join("\n")).unwrap_err();
+        assert_eq!(4, err.line);
+        assert!(err.to_string().contains(&format!("expected '{}' of the compiled MONAD program", PROGRAM[3])));
+
+        assert_eq!(PROGRAM.len(), check_program(&PROGRAM[..PROGRAM.len() - 1].join("\n")).unwrap_err().line);
+        assert_eq!(PROGRAM.len() + 1, check_program(&format!("{}\nadd x 1", PROGRAM.join("\n"))).unwrap_err().line);
+    }
+}
+
+/// Tests for the cancellation of the search
+#[cfg(test)]
+mod tests_budget {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "search for model numbers was cancelled")]
+    fn test_cancelled_search() {
+        let budget = Budget::unlimited();
+        budget.cancel();
+        assert_eq!(None, find_model_numbers(&budget));
+        budget.install(|| Day24::part1(&ModelNumbers::default()));
+    }
+}
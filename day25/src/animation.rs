@@ -0,0 +1,82 @@
+//! Animation of the moving herds
+
+use aoc_utils::{Animation, Frame, Grid, Palette, ParseError, Rgb, Solution, Visualization};
+
+use crate::{step, Cell, Day25};
+
+/// Shows the herds step by step until they stop
+pub const VISUALIZATION: Visualization = Visualization {
+    day: 25,
+    description: "sea cucumbers moving until they stop",
+    create,
+};
+
+fn create(input: &str) -> Result<Box<dyn Animation>, ParseError> {
+    Ok(Box::new(Herds::new(Day25::parse(input)?)))
+}
+
+/// Sea floor after each step
+pub struct Herds {
+    floor: Grid<Cell>,
+    steps: usize,
+    stopped: bool,
+}
+
+impl Herds {
+    /// Starts with the initial sea floor
+    pub fn new(floor: Grid<Cell>) -> Self {
+        Herds { floor, steps: 0, stopped: false }
+    }
+}
+
+impl Animation for Herds {
+    fn frame(&self) -> Frame {
+        let title = match self.stopped {
+            true => format!("After {} steps, nothing moves on step {}", self.steps, self.steps + 1),
+            false => format!("After {} steps", self.steps),
+        };
+        Frame::new(title, &self.floor.to_string())
+    }
+
+    fn step(&mut self) -> bool {
+        if self.stopped {
+            return false;
+        }
+
+        match step(&self.floor) {
+            Some(floor) => {
+                self.floor = floor;
+                self.steps += 1;
+            }
+            None => self.stopped = true,
+        }
+
+        true
+    }
+
+    fn palette(&self) -> Palette {
+        &[('>', Rgb(90, 220, 120)), ('v', Rgb(90, 170, 240)), ('.', Rgb(40, 60, 90))]
+    }
+}
+
+#[cfg(test)]
+mod tests_animation {
+    use super::*;
+
+    #[test]
+    fn move_until_stopped() {
+        let floor = crate::parse_input(include_str!("../examples/example.txt")).unwrap();
+        let mut animation = Herds::new(floor.clone());
+        assert_eq!("After 0 steps", animation.frame().title);
+        assert_eq!(floor.to_string(), animation.frame().lines.join("\n"));
+
+        let mut frames = 1;
+        while animation.step() {
+            frames += 1;
+        }
+
+        // 57 steps with movement, the last frame shows that nothing moves anymore
+        assert_eq!(59, frames);
+        assert_eq!("After 57 steps, nothing moves on step 58", animation.frame().title);
+    }
+}
@@ -0,0 +1,49 @@
+//! Picture of the sea floor once the herds stopped
+
+use aoc_utils::{encode_ppm, Grid, ImageExport, ParseError, Rgb, Solution};
+
+use crate::{step, Cell, Day25};
+
+/// Exports the final layout of the herds
+pub const EXPORT: ImageExport = ImageExport {
+    day: 25,
+    extension: "ppm",
+    description: "sea floor after the herds stopped, east-facing herd green and south-facing herd blue",
+    render,
+};
+
+fn render(input: &str) -> Result<Vec<u8>, ParseError> {
+    Ok(render_floor(&final_floor(Day25::parse(input)?)))
+}
+
+/// Moves the herds until no sea cucumber moves anymore
+pub fn final_floor(mut floor: Grid<Cell>) -> Grid<Cell> {
+    while let Some(next) = step(&floor) {
+        floor = next;
+    }
+
+    floor
+}
+
+/// PPM with one pixel per location
+pub fn render_floor(floor: &Grid<Cell>) -> Vec<u8> {
+    encode_ppm(&floor.map(|cell| match cell {
+        Cell::Empty => Rgb(20, 30, 60),
+        Cell::East => Rgb(90, 220, 120),
+        Cell::South => Rgb(90, 170, 240),
+    }))
+}
+
+#[cfg(test)]
+mod tests_export {
+    use super::*;
+
+    #[test]
+    fn export() {
+        assert_eq!(b"P6\n3 1\n255\n\x14\x1e\x3c\x5a\xdc\x78\x5a\xaa\xf0".as_slice(), render_floor(&crate::parse_input(".>v").unwrap()));
+
+        let floor = final_floor(crate::parse_input(include_str!("../examples/example.txt")).unwrap());
+        assert!(step(&floor).is_none());
+        assert_eq!("..>>v>vv..", floor.to_string().lines().next().unwrap());
+    }
+}
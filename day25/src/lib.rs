@@ -0,0 +1,216 @@
+//! Day 25: Sea Cucumber
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_utils::{Grid, NoAnswer, ParseError, Solution};
+
+pub mod animation;
+pub mod export;
+
+/// Location on the sea floor
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Cell {
+    /// No sea cucumber (`.`)
+    Empty,
+    /// Sea cucumber of the east-facing herd (`>`)
+    East,
+    /// Sea cucumber of the south-facing herd (`v`)
+    South,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        })
+    }
+}
+
+/// Parses the map of the sea floor
+pub fn parse_input(input: &str) -> Result<Grid<Cell>, ParseError> {
+    Ok(Grid::parse(input, |c| match c {
+        '.' => Some(Cell::Empty),
+        '>' => Some(Cell::East),
+        'v' => Some(Cell::South),
+        _ => None,
+    })?)
+}
+
+/// Moves both herds once, returns `None` if no sea cucumber moved
+pub fn step(input: &Grid<Cell>) -> Option<Grid<Cell>> {
+    let mut result = Grid::filled(input.width(), input.height(), Cell::Empty);
+
+    let mut moved_something = false;
+    input.positions().filter(|(_, c)| **c == Cell::East).for_each(|(pos, _)| {
+        let mut target = input.wrapping_offset(pos, (1, 0));
+        if input[target] == Cell::Empty {
+            moved_something |= true;
+        } else {
+            target = pos;
+        }
+
+        result[target] = Cell::East;
+    });
+
+    input.positions().filter(|(_, c)| **c == Cell::South).for_each(|(pos, _)| {
+        let mut target = input.wrapping_offset(pos, (0, 1));
+        if result[target] == Cell::Empty && input[target] != Cell::South {
+            moved_something |= true;
+        } else {
+            target = pos;
+        }
+
+        result[target] = Cell::South;
+    });
+
+    if !moved_something {
+        return None;
+    }
+
+    Some(result)
+}
+
+/// Number of the first step on which no sea cucumber moves
+pub fn solve(mut input: Grid<Cell>) -> i32 {
+    let mut i = 1;
+    loop {
+        if let Some(result) = step(&input) {
+            input = result;
+            i += 1;
+        } else {
+            return i;
+        }
+    }
+}
+
+/// Solution for day 25
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: i32 = 25;
+    const HAS_PART2: bool = false;
+    type Input = Grid<Cell>;
+    type Answer1 = i32;
+    type Answer2 = NoAnswer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        solve(input.clone())
+    }
+
+    fn part2(_: &Self::Input) -> Self::Answer2 {
+        NoAnswer
+    }
+}
+
+aoc_utils::example_tests!(Day25);
+
+/// Tests for star 1
+#[cfg(test)]
+mod tests_star1 {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let result = parse_input(".v>\nv>.").unwrap();
+        assert_eq!(2, result.height());
+        assert_eq!(3, result.width());
+        assert_eq!(Cell::South, result[(0, 1)]);
+    }
+
+    #[test]
+    fn test_parse_malformed_input() {
+        let err = parse_input(".v>\nv<.").unwrap_err();
+        assert_eq!("line 2, column 2: invalid cell '<' at row 2, column 2", err.to_string());
+        let err = parse_input(".v>\nv>").unwrap_err();
+        assert_eq!("line 2, column 1: row 2 has 2 cells, expected 3", err.to_string());
+    }
+
+    #[test]
+    fn test_parse_input_line_endings() {
+        for input in aoc_utils::line_ending_variants(".v>\nv>.") {
+            assert_eq!(".v>\nv>.", parse_input(&input).unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_to_string() {
+        let result = parse_input(".v>\nv>.").unwrap();
+        assert_eq!(".v>\nv>.", result.to_string());
+    }
+
+    #[test]
+    fn test_simple_step() {
+        let input = parse_input("..........
+.>v....v..
+.......>..
+..........").unwrap();
+        let result = step(&input);
+        assert_eq!("..........
+.>........
+..v....v>.
+..........", result.unwrap().to_string());
+    }
+
+
+    #[test]
+    fn test_multi_step() {
+        let input = parse_input("...>...
+.......
+......>
+v.....>
+......>
+.......
+..vvv..").unwrap();
+        let result = step(&input).unwrap();
+        assert_eq!("..vv>..
+.......
+>......
+v.....>
+>......
+.......
+....v..", result.to_string());
+        let result = step(&result).unwrap();
+        assert_eq!("....v>.
+..vv...
+.>.....
+......>
+v>.....
+.......
+.......", result.to_string());
+    }
+    
+    #[test]
+    fn test_no_move() {
+        let input = parse_input("..>>v>vv..
+..v.>>vv..
+..>>v>>vv.
+..>>>>>vv.
+v......>vv
+v>v....>>v
+vvv.....>>
+>vv......>
+.>v.vv.v..").unwrap();
+        assert!(step(&input).is_none());
+    }
+    
+    #[test]
+    fn test_stopping() {
+        let input = parse_input("v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>").unwrap();
+        assert_eq!(58, solve(input));
+    }
+}